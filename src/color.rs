@@ -0,0 +1,630 @@
+//! Colors are parsed from `#rrggbb`, `#rgb`, `rgb(r, g, b)`, ANSI 256 palette indices like `208`,
+//! and names. The eight ANSI names `black`, `red`, `green`, `yellow`, `blue`, `magenta`, `cyan`
+//! and `white` and their `bright_` versions are the terminal palette colors, every other CSS
+//! named color like `orange` or `rebeccapurple` is its RGB value. Names and hex digits are case
+//! insensitive.
+//!
+//! Downsampling picks the palette color with the smallest euclidean distance in RGB, using the
+//! default xterm palette for the first 16 colors.
+//!
+//! ```rust
+//! use konster::color::Color;
+//!
+//! const ACCENT: Color = Color::parse("#ff8700");
+//! const _: () = assert!(ACCENT.to_ansi256() == 208);
+//! assert_eq!(ACCENT.to_ansi16(), Color::Yellow);
+//! assert_eq!(Color::parse("rebeccapurple"), Color::Rgb(0x66, 0x33, 0x99));
+//! assert_eq!(Color::parse("red").to_rgb(), (205, 0, 0));
+//! ```
+use crate::error::impl_error;
+use crate::kstr::{hex_digit, parse_u64};
+
+/// Error returned when parsing a malformed color
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ColorError {
+    /// What is wrong with the color
+    pub kind: ColorErrorKind,
+    /// Byte index in the input where the error was found
+    pub offset: usize,
+}
+
+/// The different ways a color can be malformed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorErrorKind {
+    /// Not a known color name, hex color, `rgb()` color or palette index
+    UnknownColor,
+    /// A `#` not followed by 3 or 6 hex digits
+    InvalidHex,
+    /// An `rgb()` color that is not three comma separated numbers
+    InvalidRgb,
+    /// A component or palette index above 255
+    OutOfRange,
+}
+
+impl ColorErrorKind {
+    /// Returns a description of the error.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            ColorErrorKind::UnknownColor => "unknown color",
+            ColorErrorKind::InvalidHex => "invalid hex color",
+            ColorErrorKind::InvalidRgb => "invalid rgb() color",
+            ColorErrorKind::OutOfRange => "color value out of range",
+        }
+    }
+}
+
+impl ColorError {
+    const fn new(kind: ColorErrorKind, offset: usize) -> Self {
+        Self { kind, offset }
+    }
+}
+impl_error!(ColorError, " at byte " offset);
+
+/// A terminal color: one of the 16 palette colors, an index in the 256 colors palette or a truecolor
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Color {
+    /// Palette color 0
+    Black,
+    /// Palette color 1
+    Red,
+    /// Palette color 2
+    Green,
+    /// Palette color 3
+    Yellow,
+    /// Palette color 4
+    Blue,
+    /// Palette color 5
+    Magenta,
+    /// Palette color 6
+    Cyan,
+    /// Palette color 7
+    White,
+    /// Palette color 8
+    BrightBlack,
+    /// Palette color 9
+    BrightRed,
+    /// Palette color 10
+    BrightGreen,
+    /// Palette color 11
+    BrightYellow,
+    /// Palette color 12
+    BrightBlue,
+    /// Palette color 13
+    BrightMagenta,
+    /// Palette color 14
+    BrightCyan,
+    /// Palette color 15
+    BrightWhite,
+    /// A color of the 256 colors palette
+    Color256(u8),
+    /// A truecolor
+    Rgb(u8, u8, u8),
+}
+
+/// The 16 palette colors in palette order
+const ANSI16: [Color; 16] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::White,
+    Color::BrightBlack,
+    Color::BrightRed,
+    Color::BrightGreen,
+    Color::BrightYellow,
+    Color::BrightBlue,
+    Color::BrightMagenta,
+    Color::BrightCyan,
+    Color::BrightWhite,
+];
+
+/// The names of the 16 palette colors in palette order
+const ANSI16_NAMES: [&str; 16] = [
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "magenta",
+    "cyan",
+    "white",
+    "bright_black",
+    "bright_red",
+    "bright_green",
+    "bright_yellow",
+    "bright_blue",
+    "bright_magenta",
+    "bright_cyan",
+    "bright_white",
+];
+
+/// The RGB values of the 16 palette colors in xterm
+const XTERM16: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// The levels of each channel in the 6x6x6 color cube of the 256 colors palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+impl Color {
+    /// Parses a color.
+    /// # Panics: panic if the color is malformed
+    pub const fn parse(s: &str) -> Self {
+        match Self::try_parse(s) {
+            Ok(color) => color,
+            Err(err) => err.panic(),
+        }
+    }
+
+    /// Parses a color, returning an error if it is malformed.
+    pub const fn try_parse(s: &str) -> Result<Self, ColorError> {
+        let bytes = s.as_bytes();
+        match bytes {
+            [b'#', ..] => hex(s),
+            [b'r' | b'R', b'g' | b'G', b'b' | b'B', b'(', ..] => rgb(s),
+            [b'0'..=b'9', ..] => {
+                let mut idx = 0;
+                while idx < bytes.len() {
+                    if !bytes[idx].is_ascii_digit() {
+                        return Err(ColorError::new(ColorErrorKind::UnknownColor, 0));
+                    }
+                    idx += 1;
+                }
+                match parse_u64(s) {
+                    Ok(n) if n <= 255 => Ok(Color::Color256(n as u8)),
+                    _ => Err(ColorError::new(ColorErrorKind::OutOfRange, 0)),
+                }
+            }
+            _ => {
+                let mut idx = 0;
+                while idx < ANSI16_NAMES.len() {
+                    if ANSI16_NAMES[idx].eq_ignore_ascii_case(s) {
+                        return Ok(ANSI16[idx]);
+                    }
+                    idx += 1;
+                }
+                idx = 0;
+                while idx < CSS_COLORS.len() {
+                    if CSS_COLORS[idx].0.eq_ignore_ascii_case(s) {
+                        let rgb = CSS_COLORS[idx].1;
+                        return Ok(Color::Rgb((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8));
+                    }
+                    idx += 1;
+                }
+                Err(ColorError::new(ColorErrorKind::UnknownColor, 0))
+            }
+        }
+    }
+
+    /// Returns the RGB value of the color, palette colors use the xterm palette
+    pub const fn to_rgb(self) -> (u8, u8, u8) {
+        match self {
+            Color::Rgb(r, g, b) => (r, g, b),
+            Color::Color256(n) if n < 16 => XTERM16[n as usize],
+            Color::Color256(n) if n < 232 => {
+                let n = n - 16;
+                (
+                    CUBE_LEVELS[(n / 36) as usize],
+                    CUBE_LEVELS[(n / 6 % 6) as usize],
+                    CUBE_LEVELS[(n % 6) as usize],
+                )
+            }
+            Color::Color256(n) => {
+                let level = 8 + 10 * (n - 232);
+                (level, level, level)
+            }
+            color => XTERM16[color.ansi16_index() as usize],
+        }
+    }
+
+    /// Returns the index of the color in the 256 colors palette.
+    ///
+    /// Truecolors are mapped to the nearest color of the color cube or the grayscale ramp, the
+    /// first 16 colors are skipped since terminals often change them.
+    pub const fn to_ansi256(self) -> u8 {
+        let (r, g, b) = match self {
+            Color::Color256(n) => return n,
+            Color::Rgb(r, g, b) => (r, g, b),
+            color => return color.ansi16_index(),
+        };
+        let (ri, gi, bi) = (cube_index(r), cube_index(g), cube_index(b));
+        let cube = (
+            CUBE_LEVELS[ri as usize],
+            CUBE_LEVELS[gi as usize],
+            CUBE_LEVELS[bi as usize],
+        );
+        let average = (r as u32 + g as u32 + b as u32) / 3;
+        let gray_index = if average < 8 {
+            0
+        } else if average > 238 {
+            23
+        } else {
+            (average - 8 + 5) / 10
+        };
+        let gray = (8 + 10 * gray_index) as u8;
+        if distance((r, g, b), (gray, gray, gray)) < distance((r, g, b), cube) {
+            232 + gray_index as u8
+        } else {
+            16 + 36 * ri + 6 * gi + bi
+        }
+    }
+
+    /// Returns the nearest of the 16 palette colors.
+    pub const fn to_ansi16(self) -> Color {
+        let rgb = match self {
+            Color::Color256(n) if n < 16 => return ANSI16[n as usize],
+            Color::Color256(_) | Color::Rgb(..) => self.to_rgb(),
+            color => return color,
+        };
+        let mut nearest = 0;
+        let mut idx = 1;
+        while idx < 16 {
+            if distance(rgb, XTERM16[idx]) < distance(rgb, XTERM16[nearest]) {
+                nearest = idx;
+            }
+            idx += 1;
+        }
+        ANSI16[nearest]
+    }
+
+    /// Returns the palette index of one of the 16 palette colors.
+    const fn ansi16_index(self) -> u8 {
+        match self {
+            Color::Black => 0,
+            Color::Red => 1,
+            Color::Green => 2,
+            Color::Yellow => 3,
+            Color::Blue => 4,
+            Color::Magenta => 5,
+            Color::Cyan => 6,
+            Color::White => 7,
+            Color::BrightBlack => 8,
+            Color::BrightRed => 9,
+            Color::BrightGreen => 10,
+            Color::BrightYellow => 11,
+            Color::BrightBlue => 12,
+            Color::BrightMagenta => 13,
+            Color::BrightCyan => 14,
+            Color::BrightWhite => 15,
+            Color::Color256(_) | Color::Rgb(..) => unreachable!(),
+        }
+    }
+}
+
+/// Returns the index of the nearest color cube level.
+const fn cube_index(v: u8) -> u8 {
+    if v < 48 {
+        0
+    } else if v < 115 {
+        1
+    } else {
+        (v - 35) / 40
+    }
+}
+
+/// Returns the squared euclidean distance between two colors.
+const fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let dr = a.0.abs_diff(b.0) as u32;
+    let dg = a.1.abs_diff(b.1) as u32;
+    let db = a.2.abs_diff(b.2) as u32;
+    dr * dr + dg * dg + db * db
+}
+
+/// Parses `#rgb` or `#rrggbb`.
+const fn hex(s: &str) -> Result<Color, ColorError> {
+    let bytes = s.as_bytes();
+    if bytes.len() != 4 && bytes.len() != 7 {
+        return Err(ColorError::new(ColorErrorKind::InvalidHex, 0));
+    }
+    let mut digits = [0; 6];
+    let mut idx = 1;
+    while idx < bytes.len() {
+        digits[idx - 1] = match hex_digit(bytes[idx]) {
+            Some(digit) => digit,
+            None => return Err(ColorError::new(ColorErrorKind::InvalidHex, idx)),
+        };
+        idx += 1;
+    }
+    Ok(if bytes.len() == 4 {
+        Color::Rgb(digits[0] * 17, digits[1] * 17, digits[2] * 17)
+    } else {
+        Color::Rgb(
+            digits[0] << 4 | digits[1],
+            digits[2] << 4 | digits[3],
+            digits[4] << 4 | digits[5],
+        )
+    })
+}
+
+/// Parses `rgb(r, g, b)`.
+const fn rgb(s: &str) -> Result<Color, ColorError> {
+    let bytes = s.as_bytes();
+    let mut components = [0; 3];
+    let mut idx = 4;
+    let mut component = 0;
+    while component < 3 {
+        while idx < bytes.len() && bytes[idx] == b' ' {
+            idx += 1;
+        }
+        let start = idx;
+        while idx < bytes.len() && bytes[idx].is_ascii_digit() {
+            idx += 1;
+        }
+        if idx == start {
+            return Err(ColorError::new(ColorErrorKind::InvalidRgb, idx));
+        }
+        components[component] = match parse_u64(s.split_at(idx).0.split_at(start).1) {
+            Ok(n) if n <= 255 => n as u8,
+            _ => return Err(ColorError::new(ColorErrorKind::OutOfRange, start)),
+        };
+        while idx < bytes.len() && bytes[idx] == b' ' {
+            idx += 1;
+        }
+        let sep = if component == 2 { b')' } else { b',' };
+        if idx == bytes.len() || bytes[idx] != sep {
+            return Err(ColorError::new(ColorErrorKind::InvalidRgb, idx));
+        }
+        idx += 1;
+        component += 1;
+    }
+    if idx != bytes.len() {
+        return Err(ColorError::new(ColorErrorKind::InvalidRgb, idx));
+    }
+    Ok(Color::Rgb(components[0], components[1], components[2]))
+}
+
+/// The CSS named colors, the ANSI names take precedence over them
+const CSS_COLORS: [(&str, u32); 148] = [
+    ("aliceblue", 0xf0f8ff),
+    ("antiquewhite", 0xfaebd7),
+    ("aqua", 0x00ffff),
+    ("aquamarine", 0x7fffd4),
+    ("azure", 0xf0ffff),
+    ("beige", 0xf5f5dc),
+    ("bisque", 0xffe4c4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xffebcd),
+    ("blue", 0x0000ff),
+    ("blueviolet", 0x8a2be2),
+    ("brown", 0xa52a2a),
+    ("burlywood", 0xdeb887),
+    ("cadetblue", 0x5f9ea0),
+    ("chartreuse", 0x7fff00),
+    ("chocolate", 0xd2691e),
+    ("coral", 0xff7f50),
+    ("cornflowerblue", 0x6495ed),
+    ("cornsilk", 0xfff8dc),
+    ("crimson", 0xdc143c),
+    ("cyan", 0x00ffff),
+    ("darkblue", 0x00008b),
+    ("darkcyan", 0x008b8b),
+    ("darkgoldenrod", 0xb8860b),
+    ("darkgray", 0xa9a9a9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xa9a9a9),
+    ("darkkhaki", 0xbdb76b),
+    ("darkmagenta", 0x8b008b),
+    ("darkolivegreen", 0x556b2f),
+    ("darkorange", 0xff8c00),
+    ("darkorchid", 0x9932cc),
+    ("darkred", 0x8b0000),
+    ("darksalmon", 0xe9967a),
+    ("darkseagreen", 0x8fbc8f),
+    ("darkslateblue", 0x483d8b),
+    ("darkslategray", 0x2f4f4f),
+    ("darkslategrey", 0x2f4f4f),
+    ("darkturquoise", 0x00ced1),
+    ("darkviolet", 0x9400d3),
+    ("deeppink", 0xff1493),
+    ("deepskyblue", 0x00bfff),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1e90ff),
+    ("firebrick", 0xb22222),
+    ("floralwhite", 0xfffaf0),
+    ("forestgreen", 0x228b22),
+    ("fuchsia", 0xff00ff),
+    ("gainsboro", 0xdcdcdc),
+    ("ghostwhite", 0xf8f8ff),
+    ("gold", 0xffd700),
+    ("goldenrod", 0xdaa520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xadff2f),
+    ("grey", 0x808080),
+    ("honeydew", 0xf0fff0),
+    ("hotpink", 0xff69b4),
+    ("indianred", 0xcd5c5c),
+    ("indigo", 0x4b0082),
+    ("ivory", 0xfffff0),
+    ("khaki", 0xf0e68c),
+    ("lavender", 0xe6e6fa),
+    ("lavenderblush", 0xfff0f5),
+    ("lawngreen", 0x7cfc00),
+    ("lemonchiffon", 0xfffacd),
+    ("lightblue", 0xadd8e6),
+    ("lightcoral", 0xf08080),
+    ("lightcyan", 0xe0ffff),
+    ("lightgoldenrodyellow", 0xfafad2),
+    ("lightgray", 0xd3d3d3),
+    ("lightgreen", 0x90ee90),
+    ("lightgrey", 0xd3d3d3),
+    ("lightpink", 0xffb6c1),
+    ("lightsalmon", 0xffa07a),
+    ("lightseagreen", 0x20b2aa),
+    ("lightskyblue", 0x87cefa),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xb0c4de),
+    ("lightyellow", 0xffffe0),
+    ("lime", 0x00ff00),
+    ("limegreen", 0x32cd32),
+    ("linen", 0xfaf0e6),
+    ("magenta", 0xff00ff),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66cdaa),
+    ("mediumblue", 0x0000cd),
+    ("mediumorchid", 0xba55d3),
+    ("mediumpurple", 0x9370db),
+    ("mediumseagreen", 0x3cb371),
+    ("mediumslateblue", 0x7b68ee),
+    ("mediumspringgreen", 0x00fa9a),
+    ("mediumturquoise", 0x48d1cc),
+    ("mediumvioletred", 0xc71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xf5fffa),
+    ("mistyrose", 0xffe4e1),
+    ("moccasin", 0xffe4b5),
+    ("navajowhite", 0xffdead),
+    ("navy", 0x000080),
+    ("oldlace", 0xfdf5e6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6b8e23),
+    ("orange", 0xffa500),
+    ("orangered", 0xff4500),
+    ("orchid", 0xda70d6),
+    ("palegoldenrod", 0xeee8aa),
+    ("palegreen", 0x98fb98),
+    ("paleturquoise", 0xafeeee),
+    ("palevioletred", 0xdb7093),
+    ("papayawhip", 0xffefd5),
+    ("peachpuff", 0xffdab9),
+    ("peru", 0xcd853f),
+    ("pink", 0xffc0cb),
+    ("plum", 0xdda0dd),
+    ("powderblue", 0xb0e0e6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xff0000),
+    ("rosybrown", 0xbc8f8f),
+    ("royalblue", 0x4169e1),
+    ("saddlebrown", 0x8b4513),
+    ("salmon", 0xfa8072),
+    ("sandybrown", 0xf4a460),
+    ("seagreen", 0x2e8b57),
+    ("seashell", 0xfff5ee),
+    ("sienna", 0xa0522d),
+    ("silver", 0xc0c0c0),
+    ("skyblue", 0x87ceeb),
+    ("slateblue", 0x6a5acd),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xfffafa),
+    ("springgreen", 0x00ff7f),
+    ("steelblue", 0x4682b4),
+    ("tan", 0xd2b48c),
+    ("teal", 0x008080),
+    ("thistle", 0xd8bfd8),
+    ("tomato", 0xff6347),
+    ("turquoise", 0x40e0d0),
+    ("violet", 0xee82ee),
+    ("wheat", 0xf5deb3),
+    ("white", 0xffffff),
+    ("whitesmoke", 0xf5f5f5),
+    ("yellow", 0xffff00),
+    ("yellowgreen", 0x9acd32),
+];
+
+#[cfg(test)]
+mod color_test {
+    use super::*;
+
+    const ORANGE: Color = Color::parse("Orange");
+    const _: () = {
+        assert!(matches!(ORANGE, Color::Rgb(255, 165, 0)));
+        assert!(matches!(Color::parse("#0a0"), Color::Rgb(0, 170, 0)));
+        assert!(Color::parse("rgb(95, 135, 175)").to_ansi256() == 67);
+    };
+
+    #[test]
+    fn parse() {
+        for (s, color) in [
+            ("#FF8700", Color::Rgb(255, 135, 0)),
+            ("#f80", Color::Rgb(255, 136, 0)),
+            ("rgb(1,2,3)", Color::Rgb(1, 2, 3)),
+            ("RGB( 255 , 0 ,10 )", Color::Rgb(255, 0, 10)),
+            ("0", Color::Color256(0)),
+            ("255", Color::Color256(255)),
+            ("red", Color::Red),
+            ("BRIGHT_CYAN", Color::BrightCyan),
+            ("maroon", Color::Rgb(128, 0, 0)),
+            ("aqua", Color::Rgb(0, 255, 255)),
+        ] {
+            assert_eq!(Color::try_parse(s), Ok(color), "{s}");
+        }
+    }
+
+    #[test]
+    fn errors() {
+        for (s, kind, offset) in [
+            ("", ColorErrorKind::UnknownColor, 0),
+            ("reddish", ColorErrorKind::UnknownColor, 0),
+            ("12a", ColorErrorKind::UnknownColor, 0),
+            ("256", ColorErrorKind::OutOfRange, 0),
+            ("#ff87", ColorErrorKind::InvalidHex, 0),
+            ("#ff870g", ColorErrorKind::InvalidHex, 6),
+            ("rgb(1,2)", ColorErrorKind::InvalidRgb, 7),
+            ("rgb(1,2,3", ColorErrorKind::InvalidRgb, 9),
+            ("rgb(1,,3)", ColorErrorKind::InvalidRgb, 6),
+            ("rgb(1,2,3) ", ColorErrorKind::InvalidRgb, 10),
+            ("rgb(1,256,3)", ColorErrorKind::OutOfRange, 6),
+        ] {
+            assert_eq!(
+                Color::try_parse(s),
+                Err(ColorError::new(kind, offset)),
+                "{s:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn palette() {
+        for n in 0..=255 {
+            let color = Color::Color256(n);
+            assert_eq!(color.to_ansi256(), n);
+            if n >= 16 {
+                let (r, g, b) = color.to_rgb();
+                assert_eq!(Color::Rgb(r, g, b).to_ansi256(), n, "{n}");
+            }
+        }
+        assert_eq!(Color::Color256(196).to_rgb(), (255, 0, 0));
+        assert_eq!(Color::Color256(244).to_rgb(), (128, 128, 128));
+        assert_eq!(Color::BrightBlue.to_rgb(), (92, 92, 255));
+        assert_eq!(Color::Color256(9).to_ansi16(), Color::BrightRed);
+    }
+
+    #[test]
+    fn downsampling() {
+        for (color, ansi256, ansi16) in [
+            (Color::Rgb(0, 0, 0), 16, Color::Black),
+            (Color::Rgb(255, 255, 255), 231, Color::BrightWhite),
+            (Color::Rgb(128, 128, 128), 244, Color::BrightBlack),
+            (Color::Rgb(200, 10, 10), 160, Color::Red),
+            (Color::Rgb(10, 10, 250), 21, Color::Blue),
+            (Color::Rgb(102, 51, 153), 60, Color::BrightBlack),
+            (Color::Rgb(90, 100, 95), 59, Color::BrightBlack),
+            (Color::Cyan, 6, Color::Cyan),
+        ] {
+            assert_eq!(color.to_ansi256(), ansi256, "{color:?}");
+            assert_eq!(color.to_ansi16(), ansi16, "{color:?}");
+        }
+    }
+}
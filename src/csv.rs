@@ -0,0 +1,552 @@
+//! The format is RFC 4180: records end with CRLF or LF, fields are separated by `,`, and a field
+//! in double quotes can hold commas, line breaks and quotes written as `""`. The first record is
+//! the header naming the columns, and every record must have as many fields as the header.
+//! Spaces are part of the fields, so `1, 2` has the fields `1` and ` 2`.
+//!
+//! Errors give the row of the record, the header being row 1, and the column of the field,
+//! starting at 1.
+//!
+//! ```rust
+//! use konster::csv::KCsv;
+//! use konster::kvec::KVec;
+//!
+//! const TABLE: KCsv = KCsv::parse(
+//!     "code,name,price\r\n\
+//!      1,apple,120\r\n\
+//!      2,\"pear, green\",-5\r\n",
+//! );
+//! const PRICES: KVec<i64, 4> = match TABLE.column_i64("price") {
+//!     Ok(prices) => prices,
+//!     Err(err) => err.panic(),
+//! };
+//! assert_eq!(PRICES.as_slice(), &[120, -5]);
+//! assert_eq!(TABLE.column_str::<4, 16>("name").unwrap().buf[1].as_str(), "pear, green");
+//! ```
+use crate::error::impl_error;
+use crate::kstr::{parse_i64, parse_u64, KStr, ParseIntErrorKind};
+use crate::kvec::KVec;
+
+/// Error returned when reading a malformed CSV
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CsvError {
+    /// What is wrong with the CSV
+    pub kind: CsvErrorKind,
+    /// Record of the error, starting at 1 with the header
+    pub row: usize,
+    /// Field of the error, starting at 1
+    pub column: usize,
+}
+
+/// The different ways a CSV can be malformed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CsvErrorKind {
+    /// A quoted field without its closing quote
+    UnterminatedQuote,
+    /// A quote inside an unquoted field, or chars after the closing quote of a field
+    InvalidQuote,
+    /// A record with more or fewer fields than the header, the column is the first extra or missing one
+    FieldCount,
+    /// A field that is not a valid integer for the column type
+    InvalidInteger(ParseIntErrorKind),
+    /// A column name that is not in the header, the column is the one after the last header field
+    UnknownColumn,
+}
+
+impl CsvErrorKind {
+    /// Returns a description of the error.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            CsvErrorKind::UnterminatedQuote => "unterminated quoted field",
+            CsvErrorKind::InvalidQuote => "misplaced quote",
+            CsvErrorKind::FieldCount => "wrong number of fields",
+            CsvErrorKind::InvalidInteger(ParseIntErrorKind::Empty) => "empty integer",
+            CsvErrorKind::InvalidInteger(ParseIntErrorKind::InvalidDigit) => "invalid integer",
+            CsvErrorKind::InvalidInteger(ParseIntErrorKind::Overflow) => "integer out of range",
+            CsvErrorKind::UnknownColumn => "unknown column",
+        }
+    }
+}
+
+impl CsvError {
+    const fn new(kind: CsvErrorKind, row: usize, column: usize) -> Self {
+        Self { kind, row, column }
+    }
+}
+impl_error!(CsvError, " at row " row, " column " column);
+
+/// A validated CSV borrowing its text, columns are read by their header name
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KCsv<'a> {
+    text: &'a str,
+    /// Byte index of the first record after the header
+    body: usize,
+    columns: usize,
+    rows: usize,
+}
+
+impl<'a> KCsv<'a> {
+    /// Checks the quoting and the field counts of a CSV.
+    /// # Panics: panic if the CSV is malformed
+    pub const fn parse(text: &'a str) -> Self {
+        match Self::try_parse(text) {
+            Ok(csv) => csv,
+            Err(err) => err.panic(),
+        }
+    }
+    /// Checks the quoting and the field counts of a CSV, or returns where it is malformed.
+    pub const fn try_parse(text: &'a str) -> Result<Self, CsvError> {
+        let mut idx = 0;
+        let mut columns = 0;
+        loop {
+            let field = match Field::read(text, idx, 1, columns + 1) {
+                Ok(field) => field,
+                Err(err) => return Err(err),
+            };
+            columns += 1;
+            idx = field.next;
+            if field.last {
+                break;
+            }
+        }
+        let body = idx;
+        let mut rows = 0;
+        while idx < text.len() {
+            idx = match record(text, idx, rows + 2, columns, 0) {
+                Ok((_, next)) => next,
+                Err(err) => return Err(err),
+            };
+            rows += 1;
+        }
+        Ok(Self {
+            text,
+            body,
+            columns,
+            rows,
+        })
+    }
+
+    /// Returns the number of columns
+    pub const fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Returns the number of records after the header
+    pub const fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the name of the column at `index`, or None if there is no such column
+    /// # Panics: panic if the name doesn't fit in the String
+    pub const fn header<const NN: usize>(&self, index: usize) -> Option<KStr<NN>> {
+        if index >= self.columns {
+            return None;
+        }
+        match record(self.text, 0, 1, self.columns, index) {
+            Ok((field, _)) => Some(field.unquote(self.text)),
+            Err(_) => None,
+        }
+    }
+
+    /// Returns the index of the column named `name`
+    pub const fn column_index(&self, name: &str) -> Option<usize> {
+        let mut idx = 0;
+        let mut index = 0;
+        while index < self.columns {
+            let field = match Field::read(self.text, idx, 1, index + 1) {
+                Ok(field) => field,
+                Err(_) => return None,
+            };
+            if field.eq(self.text, name) {
+                return Some(index);
+            }
+            idx = field.next;
+            index += 1;
+        }
+        None
+    }
+
+    /// Returns the fields of the column named `name`, unquoted.
+    /// # Panics: panic if the Vector or one of the Strings is too small
+    pub const fn column_str<const L: usize, const NN: usize>(
+        &self,
+        name: &str,
+    ) -> Result<KVec<KStr<NN>, L>, CsvError> {
+        let mut column = match self.column(name) {
+            Ok(column) => column,
+            Err(err) => return Err(err),
+        };
+        let mut out = KVec::new(KStr::new());
+        loop {
+            out = match column.next(self) {
+                Ok(Some(field)) => out.push(field.unquote(self.text)),
+                Ok(None) => return Ok(out),
+                Err(err) => return Err(err),
+            };
+        }
+    }
+
+    /// Returns the fields of the column named `name`, parsed like [`KStr::parse_i64`].
+    /// # Panics: panic if the Vector is too small
+    pub const fn column_i64<const L: usize>(&self, name: &str) -> Result<KVec<i64, L>, CsvError> {
+        let mut column = match self.column(name) {
+            Ok(column) => column,
+            Err(err) => return Err(err),
+        };
+        let mut out = KVec::new(0);
+        loop {
+            let field = match column.next(self) {
+                Ok(Some(field)) => field.content(self.text),
+                Ok(None) => return Ok(out),
+                Err(err) => return Err(err),
+            };
+            out = match parse_i64(field) {
+                Ok(n) => out.push(n),
+                Err(err) => return Err(column.invalid_integer(err.kind)),
+            };
+        }
+    }
+
+    /// Returns the fields of the column named `name`, parsed like [`KStr::parse_u64`].
+    /// # Panics: panic if the Vector is too small
+    pub const fn column_u64<const L: usize>(&self, name: &str) -> Result<KVec<u64, L>, CsvError> {
+        let mut column = match self.column(name) {
+            Ok(column) => column,
+            Err(err) => return Err(err),
+        };
+        let mut out = KVec::new(0);
+        loop {
+            let field = match column.next(self) {
+                Ok(Some(field)) => field.content(self.text),
+                Ok(None) => return Ok(out),
+                Err(err) => return Err(err),
+            };
+            out = match parse_u64(field) {
+                Ok(n) => out.push(n),
+                Err(err) => return Err(column.invalid_integer(err.kind)),
+            };
+        }
+    }
+
+    /// Looks up the column named `name` in the header, the returned cursor walks its fields.
+    const fn column(&self, name: &str) -> Result<Column, CsvError> {
+        match self.column_index(name) {
+            Some(index) => Ok(Column {
+                index,
+                idx: self.body,
+                row: 1,
+            }),
+            None => Err(CsvError::new(
+                CsvErrorKind::UnknownColumn,
+                1,
+                self.columns + 1,
+            )),
+        }
+    }
+}
+
+/// Walks the fields of a column record by record
+#[derive(Clone, Copy)]
+struct Column {
+    index: usize,
+    /// Start of the next record
+    idx: usize,
+    /// Row of the last record read
+    row: usize,
+}
+
+impl Column {
+    /// Reads the field of the next record, or None after the last record.
+    const fn next(&mut self, csv: &KCsv) -> Result<Option<Field>, CsvError> {
+        if self.idx == csv.text.len() {
+            return Ok(None);
+        }
+        self.row += 1;
+        match record(csv.text, self.idx, self.row, csv.columns, self.index) {
+            Ok((field, next)) => {
+                self.idx = next;
+                Ok(Some(field))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Returns the error for the field read last, which is not a valid integer.
+    const fn invalid_integer(&self, kind: ParseIntErrorKind) -> CsvError {
+        CsvError::new(CsvErrorKind::InvalidInteger(kind), self.row, self.index + 1)
+    }
+}
+
+impl<const N: usize> KStr<N> {
+    /// Reads the String as a CSV, see [`KCsv::try_parse`].
+    pub const fn csv(&self) -> Result<KCsv<'_>, CsvError> {
+        KCsv::try_parse(self.as_str())
+    }
+}
+
+/// A field as byte indexes in the text
+#[derive(Clone, Copy)]
+struct Field {
+    start: usize,
+    end: usize,
+    quoted: bool,
+    /// Start of the next field or record
+    next: usize,
+    /// Whether the field ends its record
+    last: bool,
+}
+
+impl Field {
+    /// Reads the field starting at `start`, `row` and `column` are only used for errors.
+    const fn read(s: &str, start: usize, row: usize, column: usize) -> Result<Self, CsvError> {
+        let bytes = s.as_bytes();
+        let quoted = start < bytes.len() && bytes[start] == b'"';
+        let mut idx = start;
+        if quoted {
+            idx += 1;
+            loop {
+                if idx == bytes.len() {
+                    return Err(CsvError::new(CsvErrorKind::UnterminatedQuote, row, column));
+                }
+                if bytes[idx] != b'"' {
+                    idx += 1;
+                } else if idx + 1 < bytes.len() && bytes[idx + 1] == b'"' {
+                    idx += 2;
+                } else {
+                    idx += 1;
+                    break;
+                }
+            }
+        } else {
+            while idx < bytes.len() && !is_separator(bytes, idx) {
+                if bytes[idx] == b'"' {
+                    return Err(CsvError::new(CsvErrorKind::InvalidQuote, row, column));
+                }
+                idx += 1;
+            }
+        }
+        let (next, last) = if idx == bytes.len() {
+            (idx, true)
+        } else {
+            match bytes[idx] {
+                b',' => (idx + 1, false),
+                b'\n' => (idx + 1, true),
+                b'\r' if is_separator(bytes, idx) => (idx + 2, true),
+                _ => return Err(CsvError::new(CsvErrorKind::InvalidQuote, row, column)),
+            }
+        };
+        Ok(Self {
+            start,
+            end: idx,
+            quoted,
+            next,
+            last,
+        })
+    }
+
+    /// Returns the field without its quotes, doubled quotes are left as is
+    const fn content<'a>(&self, s: &'a str) -> &'a str {
+        if self.quoted {
+            s.split_at(self.end - 1).0.split_at(self.start + 1).1
+        } else {
+            s.split_at(self.end).0.split_at(self.start).1
+        }
+    }
+
+    /// Returns the field without its quotes and with doubled quotes unescaped
+    const fn unquote<const NN: usize>(&self, s: &str) -> KStr<NN> {
+        let content = self.content(s).as_bytes();
+        let mut out = KStr::new();
+        let mut chunk = 0;
+        let mut idx = 0;
+        while idx < content.len() {
+            if content[idx] == b'"' {
+                // Keep the first quote of the pair
+                out = out.extend(content.split_at(idx + 1).0.split_at(chunk).1);
+                idx += 2;
+                chunk = idx;
+            } else {
+                idx += 1;
+            }
+        }
+        out.extend(content.split_at(chunk).1)
+    }
+
+    /// Compares the unquoted field with `other`
+    const fn eq(&self, s: &str, other: &str) -> bool {
+        let content = self.content(s).as_bytes();
+        let other = other.as_bytes();
+        let mut idx = 0;
+        let mut other_idx = 0;
+        while idx < content.len() {
+            if other_idx == other.len() || content[idx] != other[other_idx] {
+                return false;
+            }
+            idx += if content[idx] == b'"' { 2 } else { 1 };
+            other_idx += 1;
+        }
+        other_idx == other.len()
+    }
+}
+
+/// Returns whether a field ends at `idx`, a lone `\r` is part of the field
+const fn is_separator(bytes: &[u8], idx: usize) -> bool {
+    match bytes[idx] {
+        b',' | b'\n' => true,
+        b'\r' => idx + 1 < bytes.len() && bytes[idx + 1] == b'\n',
+        _ => false,
+    }
+}
+
+/// Reads the record starting at `start` checking it has `columns` fields,
+/// returns its field at `index` and the start of the next record.
+const fn record(
+    s: &str,
+    start: usize,
+    row: usize,
+    columns: usize,
+    index: usize,
+) -> Result<(Field, usize), CsvError> {
+    let mut found = Field {
+        start,
+        end: start,
+        quoted: false,
+        next: start,
+        last: true,
+    };
+    let mut idx = start;
+    let mut count = 0;
+    loop {
+        let field = match Field::read(s, idx, row, count + 1) {
+            Ok(field) => field,
+            Err(err) => return Err(err),
+        };
+        if count == index {
+            found = field;
+        }
+        count += 1;
+        idx = field.next;
+        if field.last {
+            break;
+        }
+    }
+    if count != columns {
+        let column = if count < columns { count } else { columns } + 1;
+        return Err(CsvError::new(CsvErrorKind::FieldCount, row, column));
+    }
+    Ok((found, idx))
+}
+
+#[cfg(test)]
+mod csv_test {
+    use super::*;
+
+    const TABLE: KCsv = KCsv::parse("id,name\n1,\"a \"\"b\"\"\"\n2,c\n");
+    const _: () = {
+        assert!(TABLE.rows() == 2);
+        let ids: KVec<u64, 2> = match TABLE.column_u64("id") {
+            Ok(ids) => ids,
+            Err(err) => err.panic(),
+        };
+        assert!(ids.buf[1] == 2);
+    };
+
+    fn strs(text: &str, name: &str) -> Result<Vec<String>, CsvError> {
+        KCsv::try_parse(text)?
+            .column_str::<8, 32>(name)
+            .map(|fields| {
+                fields
+                    .as_slice()
+                    .iter()
+                    .map(|f| f.as_str().into())
+                    .collect()
+            })
+    }
+
+    #[test]
+    fn fields() {
+        for (text, expected) in [
+            ("h", &[][..]),
+            ("h\n", &[]),
+            ("h\r\na\r\nb", &["a", "b"]),
+            ("h\n\n\"\"\n", &["", ""]),
+            ("h\n\"a,b\"\n\"x\r\ny\"\n", &["a,b", "x\r\ny"]),
+            ("h\n\"\"\"\"\n\"a\"\"\"\n", &["\"", "a\""]),
+            ("h\n a \nb\rc\n", &[" a ", "b\rc"]),
+            ("h\né\n", &["é"]),
+        ] {
+            assert_eq!(strs(text, "h").unwrap(), expected, "{text:?}");
+        }
+        assert_eq!(strs("a,\"h\"\"\",c\n1,2,3", "h\"").unwrap(), ["2"]);
+        assert_eq!(strs("a,b,\n1,2,", "").unwrap(), [""]);
+    }
+
+    #[test]
+    fn header() {
+        let csv = KCsv::parse("a,\"b,c\",\"\"\r\n");
+        assert_eq!(csv.columns(), 3);
+        assert_eq!(csv.rows(), 0);
+        assert_eq!(csv.header::<8>(1).unwrap().as_str(), "b,c");
+        assert_eq!(csv.header::<8>(2).unwrap().as_str(), "");
+        assert!(csv.header::<8>(3).is_none());
+        assert_eq!(csv.column_index("b,c"), Some(1));
+        assert_eq!(csv.column_index(""), Some(2));
+        assert_eq!(csv.column_index("b"), None);
+        assert_eq!(KStr::<16>::from_str("x,y\n1,2").csv().unwrap().rows(), 1);
+    }
+
+    #[test]
+    fn integers() {
+        let csv = KCsv::parse("n,m\n-3,\"+4\"\n9223372036854775807,0");
+        assert_eq!(
+            csv.column_i64::<2>("n").unwrap().as_slice(),
+            &[-3, i64::MAX]
+        );
+        assert_eq!(csv.column_u64::<2>("m").unwrap().as_slice(), &[4, 0]);
+        assert_eq!(
+            csv.column_u64::<2>("n"),
+            Err(CsvError::new(
+                CsvErrorKind::InvalidInteger(ParseIntErrorKind::InvalidDigit),
+                2,
+                1
+            ))
+        );
+        let csv = KCsv::parse("a,n\nx,1\ny,\nz, 3");
+        let err = csv.column_i64::<3>("n").unwrap_err();
+        assert_eq!(err.row, 3);
+        assert_eq!(err.column, 2);
+        assert_eq!(err.to_string(), "empty integer at row 3 column 2");
+    }
+
+    #[test]
+    fn errors() {
+        for (text, kind, row, column) in [
+            ("a,\"b", CsvErrorKind::UnterminatedQuote, 1, 2),
+            ("a\n\"x\"y", CsvErrorKind::InvalidQuote, 2, 1),
+            ("a,b\n1,x\"y", CsvErrorKind::InvalidQuote, 2, 2),
+            ("a,b\n1,2\n3", CsvErrorKind::FieldCount, 3, 2),
+            ("a,b\n1,2,3\n", CsvErrorKind::FieldCount, 2, 3),
+            ("a,b\n1,2\n\n", CsvErrorKind::FieldCount, 3, 2),
+        ] {
+            assert_eq!(
+                KCsv::try_parse(text),
+                Err(CsvError::new(kind, row, column)),
+                "{text:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_column() {
+        let csv = KCsv::parse("a,b\n1,2");
+        let err = CsvError::new(CsvErrorKind::UnknownColumn, 1, 3);
+        assert_eq!(csv.column_i64::<1>("c"), Err(err));
+        assert_eq!(csv.column_str::<1, 1>("c"), Err(err));
+        assert_eq!(err.to_string(), "unknown column at row 1 column 3");
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_many_rows() {
+        let _ = KCsv::parse("a\n1\n2").column_i64::<1>("a");
+    }
+}
@@ -0,0 +1,417 @@
+use crate::error::impl_error;
+use crate::kstr::{char_at, hex_digit, KStr, HEX_LOWER, HEX_UPPER};
+use crate::kvec::KVec;
+
+/// Error returned when decoding malformed hex or base64
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DecodeError {
+    /// What is wrong with the input
+    pub kind: DecodeErrorKind,
+    /// Byte index in the input where the error was found
+    pub offset: usize,
+}
+
+/// The different ways hex or base64 input can be malformed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeErrorKind {
+    /// A char that is not part of the alphabet
+    InvalidChar(char),
+    /// Hex input with an odd number of digits, or base64 input with a dangling char
+    InvalidLength,
+    /// Base64 padding that is missing, misplaced or not allowed
+    InvalidPadding,
+    /// The last base64 char encodes bits that are not part of the output
+    TrailingBits,
+}
+
+impl DecodeErrorKind {
+    /// Returns a description of the error.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            DecodeErrorKind::InvalidChar(_) => "invalid char",
+            DecodeErrorKind::InvalidLength => "invalid length",
+            DecodeErrorKind::InvalidPadding => "invalid padding",
+            DecodeErrorKind::TrailingBits => "trailing bits",
+        }
+    }
+}
+
+impl DecodeError {
+    pub(crate) const fn new(kind: DecodeErrorKind, offset: usize) -> Self {
+        Self { kind, offset }
+    }
+}
+impl_error!(DecodeError char DecodeErrorKind::InvalidChar, " at byte " offset);
+
+/// Returns the lowercase hex encoding of the bytes in a String of capacity `M`.
+/// # Panics: panic if the result doesn't fit in `M`
+pub const fn hex_encode<const M: usize>(bytes: &[u8]) -> KStr<M> {
+    encode_hex(bytes, HEX_LOWER)
+}
+
+/// Returns the uppercase hex encoding of the bytes in a String of capacity `M`.
+/// # Panics: panic if the result doesn't fit in `M`
+pub const fn hex_encode_upper<const M: usize>(bytes: &[u8]) -> KStr<M> {
+    encode_hex(bytes, HEX_UPPER)
+}
+
+const fn encode_hex<const M: usize>(bytes: &[u8], digits: &[u8; 16]) -> KStr<M> {
+    let mut out = KStr::new();
+    let mut idx = 0;
+    while idx < bytes.len() {
+        out = out
+            .push(digits[(bytes[idx] >> 4) as usize])
+            .push(digits[(bytes[idx] & 0xf) as usize]);
+        idx += 1;
+    }
+    out
+}
+
+/// Decodes hex digits of either case into a Vector of capacity `M`.
+/// # Panics: panic if the result doesn't fit in `M`
+pub const fn hex_decode<const M: usize>(s: &str) -> Result<KVec<u8, M>, DecodeError> {
+    let bytes = s.as_bytes();
+    let mut out = KVec::new(0);
+    let mut idx = 0;
+    while idx < bytes.len() {
+        let high = match hex_value(s, idx) {
+            Ok(v) => v,
+            Err(e) => return Err(e),
+        };
+        if idx + 1 == bytes.len() {
+            return Err(DecodeError::new(DecodeErrorKind::InvalidLength, idx));
+        }
+        let low = match hex_value(s, idx + 1) {
+            Ok(v) => v,
+            Err(e) => return Err(e),
+        };
+        out = out.push(high << 4 | low);
+        idx += 2;
+    }
+    Ok(out)
+}
+
+pub(crate) const fn hex_value(s: &str, idx: usize) -> Result<u8, DecodeError> {
+    match hex_digit(s.as_bytes()[idx]) {
+        Some(v) => Ok(v),
+        None => Err(invalid_char(s, idx)),
+    }
+}
+
+/// Error for the char at the byte index, or at the start of the char it is part of.
+pub(crate) const fn invalid_char(s: &str, mut idx: usize) -> DecodeError {
+    while !s.is_char_boundary(idx) {
+        idx -= 1;
+    }
+    let c = match char_at(s, idx) {
+        Some((c, _)) => c,
+        None => unreachable!(),
+    };
+    DecodeError::new(DecodeErrorKind::InvalidChar(c), idx)
+}
+
+/// How base64 padding is handled
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Padding {
+    /// Output is padded with `=`, input must be padded
+    Padded,
+    /// Output is not padded, input must not be padded
+    Unpadded,
+    /// Output is padded with `=`, input can be padded or not
+    Indifferent,
+}
+
+/// Base64 alphabet and padding configuration
+///
+/// ```rust
+/// use konster::encoding::Base64;
+/// use konster::kvec::KVec;
+///
+/// const KEY: KVec<u8, 8> = match Base64::STANDARD.decode("a29uc3Rlcg==") {
+///     Ok(key) => key,
+///     Err(e) => e.panic(),
+/// };
+/// assert_eq!(KEY.as_slice(), b"konster");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Base64 {
+    url_safe: bool,
+    padding: Padding,
+}
+
+const BASE64_STANDARD: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64_URL_SAFE: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+impl Base64 {
+    /// Standard alphabet (RFC 4648 section 4) with padding
+    pub const STANDARD: Self = Self {
+        url_safe: false,
+        padding: Padding::Padded,
+    };
+    /// Standard alphabet (RFC 4648 section 4) without padding
+    pub const STANDARD_NO_PAD: Self = Self {
+        url_safe: false,
+        padding: Padding::Unpadded,
+    };
+    /// URL and filename safe alphabet (RFC 4648 section 5) with padding
+    pub const URL_SAFE: Self = Self {
+        url_safe: true,
+        padding: Padding::Padded,
+    };
+    /// URL and filename safe alphabet (RFC 4648 section 5) without padding
+    pub const URL_SAFE_NO_PAD: Self = Self {
+        url_safe: true,
+        padding: Padding::Unpadded,
+    };
+
+    /// Returns the configuration with a different padding.
+    #[must_use]
+    pub const fn with_padding(mut self, padding: Padding) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    const fn alphabet(&self) -> &'static [u8; 64] {
+        if self.url_safe {
+            BASE64_URL_SAFE
+        } else {
+            BASE64_STANDARD
+        }
+    }
+
+    /// Returns the base64 encoding of the bytes in a String of capacity `M`.
+    /// # Panics: panic if the result doesn't fit in `M`
+    pub const fn encode<const M: usize>(&self, bytes: &[u8]) -> KStr<M> {
+        let alphabet = self.alphabet();
+        let mut out = KStr::new();
+        let mut idx = 0;
+        while idx < bytes.len() {
+            let remaining = bytes.len() - idx;
+            let b0 = bytes[idx] as u32;
+            let b1 = if remaining > 1 {
+                bytes[idx + 1] as u32
+            } else {
+                0
+            };
+            let b2 = if remaining > 2 {
+                bytes[idx + 2] as u32
+            } else {
+                0
+            };
+            let group = b0 << 16 | b1 << 8 | b2;
+            let chars = if remaining >= 3 { 4 } else { remaining + 1 };
+            let mut c = 0;
+            while c < 4 {
+                if c < chars {
+                    out = out.push(alphabet[(group >> (18 - 6 * c) & 0x3f) as usize]);
+                } else if !matches!(self.padding, Padding::Unpadded) {
+                    out = out.push(b'=');
+                }
+                c += 1;
+            }
+            idx += 3;
+        }
+        out
+    }
+
+    /// Decodes base64 into a Vector of capacity `M`.
+    /// # Panics: panic if the result doesn't fit in `M`
+    pub const fn decode<const M: usize>(&self, s: &str) -> Result<KVec<u8, M>, DecodeError> {
+        let bytes = s.as_bytes();
+        let mut out = KVec::new(0);
+
+        // Split off the padding
+        let mut data_len = bytes.len();
+        while data_len > 0 && bytes[data_len - 1] == b'=' {
+            data_len -= 1;
+        }
+        let padding = bytes.len() - data_len;
+        if padding > 0 {
+            let expected = (4 - data_len % 4) % 4;
+            if matches!(self.padding, Padding::Unpadded) || padding != expected {
+                return Err(DecodeError::new(DecodeErrorKind::InvalidPadding, data_len));
+            }
+        } else if matches!(self.padding, Padding::Padded) && !data_len.is_multiple_of(4) {
+            return Err(DecodeError::new(DecodeErrorKind::InvalidPadding, data_len));
+        }
+
+        let mut idx = 0;
+        while idx < data_len {
+            let chars = if data_len - idx >= 4 {
+                4
+            } else {
+                data_len - idx
+            };
+            if chars == 1 {
+                return Err(DecodeError::new(DecodeErrorKind::InvalidLength, idx));
+            }
+            let mut group = 0;
+            let mut c = 0;
+            while c < chars {
+                let value = match self.value(s, idx + c) {
+                    Ok(value) => value,
+                    Err(e) => return Err(e),
+                };
+                group |= (value as u32) << (18 - 6 * c);
+                c += 1;
+            }
+            // 4 chars hold 3 bytes, 3 chars hold 2 and 2 chars hold 1.
+            let out_bytes = chars - 1;
+            let unused = (group << (8 * out_bytes)) & 0xff_ffff;
+            if unused != 0 {
+                return Err(DecodeError::new(
+                    DecodeErrorKind::TrailingBits,
+                    idx + chars - 1,
+                ));
+            }
+            let mut b = 0;
+            while b < out_bytes {
+                out = out.push((group >> (16 - 8 * b)) as u8);
+                b += 1;
+            }
+            idx += 4;
+        }
+        Ok(out)
+    }
+
+    const fn value(&self, s: &str, idx: usize) -> Result<u8, DecodeError> {
+        let (c62, c63) = if self.url_safe {
+            (b'-', b'_')
+        } else {
+            (b'+', b'/')
+        };
+        match s.as_bytes()[idx] {
+            b @ b'A'..=b'Z' => Ok(b - b'A'),
+            b @ b'a'..=b'z' => Ok(b - b'a' + 26),
+            b @ b'0'..=b'9' => Ok(b - b'0' + 52),
+            b'=' => Err(DecodeError::new(DecodeErrorKind::InvalidPadding, idx)),
+            b if b == c62 => Ok(62),
+            b if b == c63 => Ok(63),
+            _ => Err(invalid_char(s, idx)),
+        }
+    }
+}
+
+impl<const N: usize> KStr<N> {
+    /// Decodes the String hex digits into a Vector of capacity `M`, see [`hex_decode`].
+    pub const fn hex_decode<const M: usize>(&self) -> Result<KVec<u8, M>, DecodeError> {
+        hex_decode(self.as_str())
+    }
+    /// Decodes the String base64 into a Vector of capacity `M`, see [`Base64::decode`].
+    pub const fn base64_decode<const M: usize>(
+        &self,
+        config: Base64,
+    ) -> Result<KVec<u8, M>, DecodeError> {
+        config.decode(self.as_str())
+    }
+}
+
+impl<const N: usize> KVec<u8, N> {
+    /// Returns the lowercase hex encoding of the Vector in a String of capacity `M`, see [`hex_encode`].
+    pub const fn hex_encode<const M: usize>(&self) -> KStr<M> {
+        hex_encode(self.as_slice())
+    }
+    /// Returns the base64 encoding of the Vector in a String of capacity `M`, see [`Base64::encode`].
+    pub const fn base64_encode<const M: usize>(&self, config: Base64) -> KStr<M> {
+        config.encode(self.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod encoding_test {
+    use super::*;
+
+    const KEY: KVec<u8, 4> = match KStr::<8>::from_str("deadBEEF").hex_decode() {
+        Ok(key) => key,
+        Err(e) => e.panic(),
+    };
+    const _: () = assert!(KEY.len() == 4 && KEY.buf[0] == 0xde && KEY.buf[3] == 0xef);
+
+    // RFC 4648 section 10 test vectors
+    const VECTORS: [(&str, &str); 7] = [
+        ("", ""),
+        ("f", "Zg=="),
+        ("fo", "Zm8="),
+        ("foo", "Zm9v"),
+        ("foob", "Zm9vYg=="),
+        ("fooba", "Zm9vYmE="),
+        ("foobar", "Zm9vYmFy"),
+    ];
+
+    #[test]
+    fn base64_vectors() {
+        for (plain, encoded) in VECTORS {
+            assert_eq!(
+                Base64::STANDARD.encode::<16>(plain.as_bytes()).as_str(),
+                encoded
+            );
+            let unpadded = encoded.trim_end_matches('=');
+            assert_eq!(
+                Base64::STANDARD_NO_PAD
+                    .encode::<16>(plain.as_bytes())
+                    .as_str(),
+                unpadded
+            );
+            let decoded = Base64::STANDARD.decode::<16>(encoded).unwrap();
+            assert_eq!(decoded.as_slice(), plain.as_bytes());
+            let decoded = Base64::STANDARD_NO_PAD.decode::<16>(unpadded).unwrap();
+            assert_eq!(decoded.as_slice(), plain.as_bytes());
+            let lenient = Base64::STANDARD.with_padding(Padding::Indifferent);
+            assert_eq!(
+                lenient.decode::<16>(unpadded).unwrap().as_slice(),
+                plain.as_bytes()
+            );
+        }
+        let bytes = [0xfb, 0xff, 0xbf];
+        assert_eq!(Base64::STANDARD.encode::<4>(&bytes).as_str(), "+/+/");
+        assert_eq!(Base64::URL_SAFE.encode::<4>(&bytes).as_str(), "-_-_");
+    }
+
+    #[test]
+    fn hex_round_trip() {
+        let bytes = KVec {
+            buf: [0x00, 0x7f, 0xab, 0xff],
+            cursor: 4,
+        };
+        assert_eq!(bytes.hex_encode::<8>().as_str(), "007fabff");
+        assert_eq!(hex_encode_upper::<8>(bytes.as_slice()).as_str(), "007FABFF");
+        assert_eq!(hex_decode::<4>("007FabfF").unwrap(), bytes);
+    }
+
+    #[test]
+    fn errors() {
+        let kind = |res: Result<KVec<u8, 8>, DecodeError>| {
+            let e = res.unwrap_err();
+            (e.kind, e.offset)
+        };
+        use DecodeErrorKind::*;
+        assert_eq!(kind(hex_decode("abc")), (InvalidLength, 2));
+        assert_eq!(kind(hex_decode("0g")), (InvalidChar('g'), 1));
+        assert_eq!(kind(hex_decode("0é")), (InvalidChar('é'), 1));
+        assert_eq!(
+            kind(Base64::STANDARD.decode("Zm9v-g==")),
+            (InvalidChar('-'), 4)
+        );
+        assert_eq!(kind(Base64::STANDARD.decode("Zg")), (InvalidPadding, 2));
+        assert_eq!(kind(Base64::STANDARD.decode("Zg=")), (InvalidPadding, 2));
+        assert_eq!(
+            kind(Base64::STANDARD_NO_PAD.decode("Zg==")),
+            (InvalidPadding, 2)
+        );
+        assert_eq!(
+            kind(Base64::STANDARD.decode("Zg==Zg==")),
+            (InvalidPadding, 2)
+        );
+        assert_eq!(
+            kind(Base64::STANDARD_NO_PAD.decode("Zm9vY")),
+            (InvalidLength, 4)
+        );
+        assert_eq!(kind(Base64::STANDARD.decode("Zh==")), (TrailingBits, 1));
+        assert_eq!(
+            hex_decode::<8>("x").unwrap_err().to_string(),
+            "invalid char 'x' at byte 0"
+        );
+    }
+}
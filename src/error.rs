@@ -0,0 +1,62 @@
+use crate::kstr::KStr;
+
+/// Message of an error, built in const context so `panic` can use it.
+pub(crate) type Message = KStr<128>;
+
+/// Appends the char to the message, quoted like its `{:?}` output.
+pub(crate) const fn quote_char(msg: Message, c: char) -> Message {
+    let msg = msg.extend(b" '");
+    let msg = match c {
+        '"' => msg.push_char(c),
+        _ => msg.extend(
+            KStr::<4>::new()
+                .push_char(c)
+                .escape_debug::<12>()
+                .as_bytes(),
+        ),
+    };
+    msg.push(b'\'')
+}
+
+/// Implements `panic`, `Display` and `Error` for an error struct with a `kind` field.
+///
+/// The message is the `as_str` of the kind followed by each label and position field.
+/// With `char Kind::Variant`, the char of that variant is quoted after the description.
+macro_rules! impl_error {
+    ($error:ident $(char $variant:path)?, $($label:literal $field:ident),+) => {
+        impl $error {
+            /// Panics with the error message, this fails the compilation in const context.
+            pub const fn panic(self) -> ! {
+                panic!("{}", self.message().as_str())
+            }
+            const fn message(&self) -> $crate::error::Message {
+                let msg = $crate::error::Message::from_str(self.kind.as_str());
+                $(let msg = match self.kind {
+                    $variant(c) => $crate::error::quote_char(msg, c),
+                    _ => msg,
+                };)?
+                msg$(.extend($label.as_bytes()).push_usize(self.$field))+
+            }
+        }
+        impl std::fmt::Display for $error {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.message().as_str())
+            }
+        }
+        impl std::error::Error for $error {}
+    };
+}
+pub(crate) use impl_error;
+
+#[cfg(test)]
+mod error_test {
+    use super::*;
+
+    #[test]
+    fn quote_char_like_debug() {
+        for c in ['a', '\'', '"', '\\', '\n', '\0', 'é', '🦀'] {
+            let msg = quote_char(Message::new(), c);
+            assert_eq!(msg.as_str(), format!(" {c:?}"));
+        }
+    }
+}
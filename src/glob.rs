@@ -0,0 +1,461 @@
+//! Patterns support `*` (any sequence of chars), `?` (any char), classes like `[abc]`, `[a-z]`
+//! or the negated `[!a-z]`/`[^a-z]`, and `\` to escape the next char.
+//! A `]` right after the opening bracket is part of the class, so `[]]` matches `]`.
+//!
+//! ```rust
+//! use konster::glob::KGlob;
+//!
+//! const ROUTE: KGlob<16> = KGlob::parse("/api/v[0-9]/*");
+//! assert!(ROUTE.matches("/api/v1/users"));
+//! assert!(!ROUTE.matches("/api/vx/users"));
+//! ```
+use crate::error::impl_error;
+use crate::kstr::{char_at, KStr};
+use crate::kvec::KVec;
+
+/// Error returned when compiling a malformed glob pattern
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GlobError {
+    /// What is wrong with the pattern
+    pub kind: GlobErrorKind,
+    /// Byte index in the pattern where the error was found
+    pub offset: usize,
+}
+
+/// The different ways a glob pattern can be malformed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GlobErrorKind {
+    /// A `[` without its closing `]`
+    UnterminatedClass,
+    /// A range like `[z-a]` whose end is before its start
+    InvalidRange,
+    /// A `\` at the end of the pattern
+    TrailingEscape,
+}
+
+impl GlobErrorKind {
+    /// Returns a short description of the error
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            GlobErrorKind::UnterminatedClass => "unterminated class",
+            GlobErrorKind::InvalidRange => "invalid range",
+            GlobErrorKind::TrailingEscape => "trailing escape",
+        }
+    }
+}
+
+impl GlobError {
+    const fn new(kind: GlobErrorKind, offset: usize) -> Self {
+        Self { kind, offset }
+    }
+}
+impl_error!(GlobError, " at byte " offset);
+
+/// A glob pattern of at most `N` bytes, compiled to its tokens
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KGlob<const N: usize> {
+    pattern: KStr<N>,
+    tokens: KVec<Token, N>,
+    classes: KVec<Class, N>,
+    /// Inclusive char ranges of the classes
+    ranges: KVec<(char, char), N>,
+}
+
+impl<const N: usize> KGlob<N> {
+    /// Compiles the pattern.
+    /// # Panics: panic if the pattern is malformed or doesn't fit in `N`
+    pub const fn parse(pattern: &str) -> Self {
+        match Self::try_parse(pattern) {
+            Ok(glob) => glob,
+            Err(err) => err.panic(),
+        }
+    }
+    /// Compiles the pattern, or returns where it is malformed.
+    /// # Panics: panic if the pattern doesn't fit in `N`
+    pub const fn try_parse(pattern: &str) -> Result<Self, GlobError> {
+        let mut glob = Self {
+            pattern: KStr::from_str(pattern),
+            tokens: KVec::new(Token::AnySeq),
+            classes: KVec::new(Class {
+                negated: false,
+                start: 0,
+                end: 0,
+            }),
+            ranges: KVec::new(('\0', '\0')),
+        };
+        let mut idx = 0;
+        while let Some((c, next)) = char_at(pattern, idx) {
+            let (token, next) = match c {
+                '*' => (Token::AnySeq, next),
+                '?' => (Token::AnyChar, next),
+                '[' => {
+                    let (negated, mut member) = class_open(pattern, idx);
+                    let first = member;
+                    let start = glob.ranges.len();
+                    let end = loop {
+                        match class_next(pattern, member, idx, member == first) {
+                            Ok((Some(range), next)) => {
+                                glob.ranges = glob.ranges.push(range);
+                                member = next;
+                            }
+                            Ok((None, end)) => break end,
+                            Err(err) => return Err(err),
+                        }
+                    };
+                    glob.classes = glob.classes.push(Class {
+                        negated,
+                        start,
+                        end: glob.ranges.len(),
+                    });
+                    (Token::Class(glob.classes.len() - 1), end)
+                }
+                '\\' => match char_at(pattern, next) {
+                    Some((c, next)) => (Token::Char(c), next),
+                    None => return Err(GlobError::new(GlobErrorKind::TrailingEscape, idx)),
+                },
+                _ => (Token::Char(c), next),
+            };
+            glob.tokens = glob.tokens.push(token);
+            idx = next;
+        }
+        Ok(glob)
+    }
+    /// Returns true if the whole text matches the pattern
+    pub const fn matches(&self, text: &str) -> bool {
+        matches(
+            Source::Compiled {
+                tokens: self.tokens.as_slice(),
+                classes: self.classes.as_slice(),
+                ranges: self.ranges.as_slice(),
+            },
+            text,
+        )
+    }
+    /// Returns the pattern
+    pub const fn as_str(&self) -> &str {
+        self.pattern.as_str()
+    }
+}
+
+/// Returns true if the whole text matches the glob pattern.
+/// # Panics: panic if the pattern is malformed
+pub const fn glob_match(pattern: &str, text: &str) -> bool {
+    if let Err(err) = validate(pattern) {
+        err.panic();
+    }
+    matches(Source::Text(pattern), text)
+}
+
+impl<const N: usize> KStr<N> {
+    /// Returns true if the whole String matches the glob pattern, see [`glob_match`].
+    /// # Panics: panic if the pattern is malformed
+    pub const fn glob_match(&self, pattern: &str) -> bool {
+        glob_match(pattern, self.as_str())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Token {
+    Char(char),
+    AnyChar,
+    AnySeq,
+    /// Byte index of the `[` opening the class in a pattern text,
+    /// or index of the class in a compiled pattern
+    Class(usize),
+}
+
+/// A compiled class, its members are the ranges from `start` to `end`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Class {
+    negated: bool,
+    start: usize,
+    end: usize,
+}
+
+/// Where the matcher reads the tokens of a valid pattern
+#[derive(Clone, Copy)]
+enum Source<'a> {
+    /// Tokens are parsed from the pattern while matching
+    Text(&'a str),
+    /// Tokens of a [`KGlob`]
+    Compiled {
+        tokens: &'a [Token],
+        classes: &'a [Class],
+        ranges: &'a [(char, char)],
+    },
+}
+
+impl Source<'_> {
+    /// Returns the token at the position and the position after it, or None at the end of the pattern.
+    const fn token(self, pos: usize) -> Option<(Token, usize)> {
+        match self {
+            Source::Text(pattern) => match char_at(pattern, pos) {
+                Some(('*', next)) => Some((Token::AnySeq, next)),
+                Some(('?', next)) => Some((Token::AnyChar, next)),
+                Some(('[', _)) => match class_end(pattern, pos) {
+                    Ok(end) => Some((Token::Class(pos), end)),
+                    Err(err) => err.panic(),
+                },
+                Some(('\\', next)) => match char_at(pattern, next) {
+                    Some((c, next)) => Some((Token::Char(c), next)),
+                    None => GlobError::new(GlobErrorKind::TrailingEscape, pos).panic(),
+                },
+                Some((c, next)) => Some((Token::Char(c), next)),
+                None => None,
+            },
+            Source::Compiled { tokens, .. } => {
+                if pos < tokens.len() {
+                    Some((tokens[pos], pos + 1))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+    /// Returns true if the char is in the class of a [`Token::Class`].
+    const fn class_contains(self, class: usize, c: char) -> bool {
+        match self {
+            Source::Text(pattern) => match class_match(pattern, class, c) {
+                Ok((found, _)) => found,
+                Err(err) => err.panic(),
+            },
+            Source::Compiled {
+                classes, ranges, ..
+            } => {
+                let class = classes[class];
+                let mut idx = class.start;
+                while idx < class.end {
+                    let (start, end) = ranges[idx];
+                    if start <= c && c <= end {
+                        return !class.negated;
+                    }
+                    idx += 1;
+                }
+                class.negated
+            }
+        }
+    }
+}
+
+/// Reads a class member, handling escapes, returns the char and the index after it.
+const fn class_char(pattern: &str, idx: usize, open: usize) -> Result<(char, usize), GlobError> {
+    match char_at(pattern, idx) {
+        Some(('\\', next)) => match char_at(pattern, next) {
+            Some(member) => Ok(member),
+            None => Err(GlobError::new(GlobErrorKind::TrailingEscape, idx)),
+        },
+        Some(member) => Ok(member),
+        None => Err(GlobError::new(GlobErrorKind::UnterminatedClass, open)),
+    }
+}
+
+/// Returns whether the class opened at `open` is negated and the index of its first member.
+const fn class_open(pattern: &str, open: usize) -> (bool, usize) {
+    let bytes = pattern.as_bytes();
+    let idx = open + 1;
+    if idx < bytes.len() && matches!(bytes[idx], b'!' | b'^') {
+        (true, idx + 1)
+    } else {
+        (false, idx)
+    }
+}
+
+/// Reads the class member or range at `idx` and returns it with the index after it,
+/// or None with the index after the closing `]`, which is a member when `first`.
+const fn class_next(
+    pattern: &str,
+    idx: usize,
+    open: usize,
+    first: bool,
+) -> Result<(Option<(char, char)>, usize), GlobError> {
+    let bytes = pattern.as_bytes();
+    if !first && idx < bytes.len() && bytes[idx] == b']' {
+        return Ok((None, idx + 1));
+    }
+    let (start, mut next) = match class_char(pattern, idx, open) {
+        Ok(member) => member,
+        Err(err) => return Err(err),
+    };
+    let mut end = start;
+    if next + 1 < bytes.len() && bytes[next] == b'-' && bytes[next + 1] != b']' {
+        (end, next) = match class_char(pattern, next + 1, open) {
+            Ok(member) => member,
+            Err(err) => return Err(err),
+        };
+        if end < start {
+            return Err(GlobError::new(GlobErrorKind::InvalidRange, idx));
+        }
+    }
+    Ok((Some((start, end)), next))
+}
+
+/// Walks the class opened at `open`, returns whether `c` is in it and the index after the `]`.
+const fn class_match(pattern: &str, open: usize, c: char) -> Result<(bool, usize), GlobError> {
+    let (negated, mut idx) = class_open(pattern, open);
+    let first = idx;
+    let mut found = false;
+    loop {
+        match class_next(pattern, idx, open, idx == first) {
+            Ok((Some((start, end)), next)) => {
+                if start <= c && c <= end {
+                    found = true;
+                }
+                idx = next;
+            }
+            Ok((None, end)) => return Ok((found != negated, end)),
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+const fn class_end(pattern: &str, open: usize) -> Result<usize, GlobError> {
+    match class_match(pattern, open, '\0') {
+        Ok((_, end)) => Ok(end),
+        Err(err) => Err(err),
+    }
+}
+
+const fn validate(pattern: &str) -> Result<(), GlobError> {
+    let mut idx = 0;
+    while let Some((c, next)) = char_at(pattern, idx) {
+        idx = match c {
+            '[' => match class_end(pattern, idx) {
+                Ok(end) => end,
+                Err(err) => return Err(err),
+            },
+            '\\' if next == pattern.len() => {
+                return Err(GlobError::new(GlobErrorKind::TrailingEscape, idx))
+            }
+            '\\' => match char_at(pattern, next) {
+                Some((_, next)) => next,
+                None => unreachable!(),
+            },
+            _ => next,
+        };
+    }
+    Ok(())
+}
+
+/// Matches a valid pattern, backtracking only to the last `*` seen.
+const fn matches(source: Source, text: &str) -> bool {
+    let (mut p, mut t) = (0, 0);
+    // Pattern position after the last `*` and the text index it was tried at
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if let Some((token, next_p)) = source.token(p) {
+            let (c, next_t) = match char_at(text, t) {
+                Some(found) => found,
+                None => unreachable!(),
+            };
+            let matched = match token {
+                Token::AnySeq => {
+                    star = Some((next_p, t));
+                    p = next_p;
+                    continue;
+                }
+                Token::Char(expected) => c == expected,
+                Token::AnyChar => true,
+                Token::Class(class) => source.class_contains(class, c),
+            };
+            if matched {
+                p = next_p;
+                t = next_t;
+                continue;
+            }
+        }
+        match star {
+            Some((star_p, star_t)) => {
+                // Let the `*` swallow one more char
+                let next_t = match char_at(text, star_t) {
+                    Some((_, next)) => next,
+                    None => unreachable!(),
+                };
+                star = Some((star_p, next_t));
+                p = star_p;
+                t = next_t;
+            }
+            None => return false,
+        }
+    }
+    while let Some((token, next)) = source.token(p) {
+        match token {
+            Token::AnySeq => p = next,
+            _ => return false,
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod glob_test {
+    use super::*;
+
+    const STATIC: KGlob<16> = KGlob::parse("*.{css,js}");
+    const _: () = {
+        assert!(glob_match("h?llo*", "héllo world"));
+        assert!(!glob_match("[!a-z]*", "abc"));
+        assert!(STATIC.matches("app.{css,js}"));
+        assert!(KStr::<8>::from_str("v1.2").glob_match("v[0-9].*"));
+    };
+
+    #[test]
+    fn matching() {
+        for (pattern, text, expected) in [
+            ("", "", true),
+            ("", "a", false),
+            ("*", "", true),
+            ("***", "abc", true),
+            ("a*b*c", "aXXbYYc", true),
+            ("a*b*c", "aXXbYYcZ", false),
+            ("*abc", "ababc", true),
+            ("a?c", "abc", true),
+            ("a?c", "ac", false),
+            ("?", "€", true),
+            ("[abc]x", "bx", true),
+            ("[a-cx-z]", "y", true),
+            ("[a-c]", "d", false),
+            ("[!a-c]", "d", true),
+            ("[^a-c]", "a", false),
+            ("[]]", "]", true),
+            ("[!]]", "]", false),
+            ("[a-]", "-", true),
+            ("[\\]]", "]", true),
+            ("\\*", "*", true),
+            ("\\*", "a", false),
+            ("[α-ω]", "λ", true),
+            ("/api/*/users", "/api/v1/users", true),
+            ("*.rs", "src/lib.rs", true),
+        ] {
+            assert_eq!(glob_match(pattern, text), expected, "{pattern} {text}");
+            let glob = KGlob::<16>::parse(pattern);
+            assert_eq!(glob.matches(text), expected, "{pattern} {text}");
+        }
+    }
+
+    #[test]
+    fn errors() {
+        for (pattern, kind, offset) in [
+            ("ab[cd", GlobErrorKind::UnterminatedClass, 2),
+            ("[]", GlobErrorKind::UnterminatedClass, 0),
+            ("x[z-a]", GlobErrorKind::InvalidRange, 2),
+            ("ab\\", GlobErrorKind::TrailingEscape, 2),
+            ("[a\\", GlobErrorKind::TrailingEscape, 2),
+        ] {
+            assert_eq!(
+                KGlob::<16>::try_parse(pattern),
+                Err(GlobError::new(kind, offset)),
+                "{pattern}"
+            );
+        }
+        assert_eq!(
+            GlobError::new(GlobErrorKind::InvalidRange, 2).to_string(),
+            "invalid range at byte 2"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn malformed() {
+        glob_match("[a", "a");
+    }
+}
@@ -0,0 +1,583 @@
+//! The hash functions take a byte slice, [`KStr`](crate::kstr::KStr) and
+//! [`KVec<u8, N>`](crate::kvec::KVec) have methods of the same name.
+//!
+//! ```rust
+//! use konster::hash::crc32;
+//! use konster::kstr::KStr;
+//!
+//! const EVENT: KStr<16> = KStr::from_str("user.login");
+//! const EVENT_ID: u64 = EVENT.fnv1a_64();
+//! const CHECKSUM: u32 = crc32(b"123456789");
+//! assert_eq!(CHECKSUM, 0xcbf4_3926);
+//! ```
+
+use crate::kstr::KStr;
+use crate::kvec::KVec;
+
+const FNV32_OFFSET: u32 = 0x811c_9dc5;
+const FNV32_PRIME: u32 = 0x0100_0193;
+const FNV64_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV64_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// 32 bits FNV-1a hash
+pub const fn fnv1a_32(bytes: &[u8]) -> u32 {
+    let mut hash = FNV32_OFFSET;
+    let mut idx = 0;
+    while idx < bytes.len() {
+        hash ^= bytes[idx] as u32;
+        hash = hash.wrapping_mul(FNV32_PRIME);
+        idx += 1;
+    }
+    hash
+}
+
+/// 64 bits FNV-1a hash
+pub const fn fnv1a_64(bytes: &[u8]) -> u64 {
+    let mut hash = FNV64_OFFSET;
+    let mut idx = 0;
+    while idx < bytes.len() {
+        hash ^= bytes[idx] as u64;
+        hash = hash.wrapping_mul(FNV64_PRIME);
+        idx += 1;
+    }
+    hash
+}
+
+/// Lookup table of the reflected CRC-32 polynomial `0xEDB88320`
+pub const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut idx = 0;
+    while idx < 256 {
+        let mut crc = idx as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[idx] = crc;
+        idx += 1;
+    }
+    table
+};
+
+/// CRC-32 checksum (ISO-HDLC, as used by zlib, gzip and PNG)
+pub const fn crc32(bytes: &[u8]) -> u32 {
+    crc32_update(0, bytes)
+}
+
+/// Continues a CRC-32 checksum with more bytes, `crc32_update(crc32(a), b)` is the checksum of `a` followed by `b`.
+pub const fn crc32_update(crc: u32, bytes: &[u8]) -> u32 {
+    let mut crc = !crc;
+    let mut idx = 0;
+    while idx < bytes.len() {
+        crc = CRC32_TABLE[((crc ^ bytes[idx] as u32) & 0xff) as usize] ^ (crc >> 8);
+        idx += 1;
+    }
+    !crc
+}
+
+/// 32 bits MurmurHash3 (x86_32)
+pub const fn murmur3_32(bytes: &[u8], seed: u32) -> u32 {
+    const C1: u32 = 0xcc9e_2d51;
+    const C2: u32 = 0x1b87_3593;
+    let mut h = seed;
+    let blocks = bytes.len() / 4;
+    let mut idx = 0;
+    while idx < blocks {
+        let mut k = read_u32(bytes, idx * 4);
+        k = k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+        h ^= k;
+        h = h.rotate_left(13).wrapping_mul(5).wrapping_add(0xe654_6b64);
+        idx += 1;
+    }
+    let tail = blocks * 4;
+    let mut k = 0;
+    let mut offset = bytes.len() - tail;
+    while offset > 0 {
+        offset -= 1;
+        k ^= (bytes[tail + offset] as u32) << (8 * offset);
+    }
+    if bytes.len() > tail {
+        h ^= k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+    }
+    h ^= bytes.len() as u32;
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x85eb_ca6b);
+    h ^= h >> 13;
+    h = h.wrapping_mul(0xc2b2_ae35);
+    h ^ (h >> 16)
+}
+
+/// 128 bits MurmurHash3 (x64_128), the first 64 bits of the reference output are the low half of the result
+pub const fn murmur3_x64_128(bytes: &[u8], seed: u32) -> u128 {
+    const C1: u64 = 0x87c3_7b91_1142_53d5;
+    const C2: u64 = 0x4cf5_ad43_2745_937f;
+    let (mut h1, mut h2) = (seed as u64, seed as u64);
+    let blocks = bytes.len() / 16;
+    let mut idx = 0;
+    while idx < blocks {
+        let k1 = read_u64(bytes, idx * 16);
+        let k2 = read_u64(bytes, idx * 16 + 8);
+        h1 ^= k1.wrapping_mul(C1).rotate_left(31).wrapping_mul(C2);
+        h1 = h1
+            .rotate_left(27)
+            .wrapping_add(h2)
+            .wrapping_mul(5)
+            .wrapping_add(0x52dc_e729);
+        h2 ^= k2.wrapping_mul(C2).rotate_left(33).wrapping_mul(C1);
+        h2 = h2
+            .rotate_left(31)
+            .wrapping_add(h1)
+            .wrapping_mul(5)
+            .wrapping_add(0x3849_5ab5);
+        idx += 1;
+    }
+    let tail = blocks * 16;
+    let rem = bytes.len() - tail;
+    let (mut k1, mut k2) = (0u64, 0u64);
+    let mut offset = 0;
+    while offset < rem {
+        let b = bytes[tail + offset] as u64;
+        if offset < 8 {
+            k1 ^= b << (8 * offset);
+        } else {
+            k2 ^= b << (8 * (offset - 8));
+        }
+        offset += 1;
+    }
+    if rem > 8 {
+        h2 ^= k2.wrapping_mul(C2).rotate_left(33).wrapping_mul(C1);
+    }
+    if rem > 0 {
+        h1 ^= k1.wrapping_mul(C1).rotate_left(31).wrapping_mul(C2);
+    }
+    h1 ^= bytes.len() as u64;
+    h2 ^= bytes.len() as u64;
+    h1 = h1.wrapping_add(h2);
+    h2 = h2.wrapping_add(h1);
+    h1 = fmix64(h1);
+    h2 = fmix64(h2);
+    h1 = h1.wrapping_add(h2);
+    h2 = h2.wrapping_add(h1);
+    (h2 as u128) << 64 | h1 as u128
+}
+
+const fn fmix64(mut k: u64) -> u64 {
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    k ^ (k >> 33)
+}
+
+const fn read_u32(bytes: &[u8], idx: usize) -> u32 {
+    u32::from_le_bytes([bytes[idx], bytes[idx + 1], bytes[idx + 2], bytes[idx + 3]])
+}
+
+const fn read_u64(bytes: &[u8], idx: usize) -> u64 {
+    read_u32(bytes, idx) as u64 | (read_u32(bytes, idx + 4) as u64) << 32
+}
+
+const PRIME32_1: u64 = 0x9e37_79b1;
+const PRIME32_2: u64 = 0x85eb_ca77;
+const PRIME32_3: u64 = 0xc2b2_ae3d;
+const PRIME64_1: u64 = 0x9e37_79b1_85eb_ca87;
+const PRIME64_2: u64 = 0xc2b2_ae3d_27d4_eb4f;
+const PRIME64_3: u64 = 0x1656_67b1_9e37_79f9;
+const PRIME64_4: u64 = 0x85eb_ca77_c2b2_ae63;
+const PRIME64_5: u64 = 0x27d4_eb2f_1656_67c5;
+const PRIME_MX1: u64 = 0x1656_6791_9e37_79f9;
+const PRIME_MX2: u64 = 0x9fb2_1c65_1e98_df25;
+
+const SECRET_SIZE: usize = 192;
+const STRIPE_LEN: usize = 64;
+const SECRET_CONSUME_RATE: usize = 8;
+const STRIPES_PER_BLOCK: usize = (SECRET_SIZE - STRIPE_LEN) / SECRET_CONSUME_RATE;
+const BLOCK_LEN: usize = STRIPE_LEN * STRIPES_PER_BLOCK;
+
+const DEFAULT_SECRET: [u8; SECRET_SIZE] = [
+    0xb8, 0xfe, 0x6c, 0x39, 0x23, 0xa4, 0x4b, 0xbe, 0x7c, 0x01, 0x81, 0x2c, 0xf7, 0x21, 0xad, 0x1c,
+    0xde, 0xd4, 0x6d, 0xe9, 0x83, 0x90, 0x97, 0xdb, 0x72, 0x40, 0xa4, 0xa4, 0xb7, 0xb3, 0x67, 0x1f,
+    0xcb, 0x79, 0xe6, 0x4e, 0xcc, 0xc0, 0xe5, 0x78, 0x82, 0x5a, 0xd0, 0x7d, 0xcc, 0xff, 0x72, 0x21,
+    0xb8, 0x08, 0x46, 0x74, 0xf7, 0x43, 0x24, 0x8e, 0xe0, 0x35, 0x90, 0xe6, 0x81, 0x3a, 0x26, 0x4c,
+    0x3c, 0x28, 0x52, 0xbb, 0x91, 0xc3, 0x00, 0xcb, 0x88, 0xd0, 0x65, 0x8b, 0x1b, 0x53, 0x2e, 0xa3,
+    0x71, 0x64, 0x48, 0x97, 0xa2, 0x0d, 0xf9, 0x4e, 0x38, 0x19, 0xef, 0x46, 0xa9, 0xde, 0xac, 0xd8,
+    0xa8, 0xfa, 0x76, 0x3f, 0xe3, 0x9c, 0x34, 0x3f, 0xf9, 0xdc, 0xbb, 0xc7, 0xc7, 0x0b, 0x4f, 0x1d,
+    0x8a, 0x51, 0xe0, 0x4b, 0xcd, 0xb4, 0x59, 0x31, 0xc8, 0x9f, 0x7e, 0xc9, 0xd9, 0x78, 0x73, 0x64,
+    0xea, 0xc5, 0xac, 0x83, 0x34, 0xd3, 0xeb, 0xc3, 0xc5, 0x81, 0xa0, 0xff, 0xfa, 0x13, 0x63, 0xeb,
+    0x17, 0x0d, 0xdd, 0x51, 0xb7, 0xf0, 0xda, 0x49, 0xd3, 0x16, 0x55, 0x26, 0x29, 0xd4, 0x68, 0x9e,
+    0x2b, 0x16, 0xbe, 0x58, 0x7d, 0x47, 0xa1, 0xfc, 0x8f, 0xf8, 0xb8, 0xd1, 0x7a, 0xd0, 0x31, 0xce,
+    0x45, 0xcb, 0x3a, 0x8f, 0x95, 0x16, 0x04, 0x28, 0xaf, 0xd7, 0xfb, 0xca, 0xbb, 0x4b, 0x40, 0x7e,
+];
+
+/// 64 bits XXH3 hash
+pub const fn xxh3_64(bytes: &[u8]) -> u64 {
+    xxh3_64_with_seed(bytes, 0)
+}
+
+/// 64 bits XXH3 hash with a seed
+pub const fn xxh3_64_with_seed(bytes: &[u8], seed: u64) -> u64 {
+    let secret = &DEFAULT_SECRET;
+    let len = bytes.len();
+    if len == 0 {
+        xxh64_avalanche(seed ^ read_u64(secret, 56) ^ read_u64(secret, 64))
+    } else if len <= 3 {
+        let combined = (bytes[0] as u32) << 16
+            | (bytes[len >> 1] as u32) << 24
+            | bytes[len - 1] as u32
+            | (len as u32) << 8;
+        let bitflip = ((read_u32(secret, 0) ^ read_u32(secret, 4)) as u64).wrapping_add(seed);
+        xxh64_avalanche(combined as u64 ^ bitflip)
+    } else if len <= 8 {
+        let seed = seed ^ ((seed as u32).swap_bytes() as u64) << 32;
+        let input =
+            (read_u32(bytes, len - 4) as u64).wrapping_add((read_u32(bytes, 0) as u64) << 32);
+        let bitflip = (read_u64(secret, 8) ^ read_u64(secret, 16)).wrapping_sub(seed);
+        rrmxmx(input ^ bitflip, len as u64)
+    } else if len <= 16 {
+        let bitflip1 = (read_u64(secret, 24) ^ read_u64(secret, 32)).wrapping_add(seed);
+        let bitflip2 = (read_u64(secret, 40) ^ read_u64(secret, 48)).wrapping_sub(seed);
+        let lo = read_u64(bytes, 0) ^ bitflip1;
+        let hi = read_u64(bytes, len - 8) ^ bitflip2;
+        avalanche(
+            (len as u64)
+                .wrapping_add(lo.swap_bytes())
+                .wrapping_add(hi)
+                .wrapping_add(mul128_fold64(lo, hi)),
+        )
+    } else if len <= 128 {
+        let mut acc = (len as u64).wrapping_mul(PRIME64_1);
+        let mut idx = 0;
+        // Mixes pairs of 16 bytes from both ends towards the middle
+        while idx < 4 && len > 32 * idx {
+            acc = acc
+                .wrapping_add(mix16(bytes, 16 * idx, secret, 32 * idx, seed))
+                .wrapping_add(mix16(
+                    bytes,
+                    len - 16 * (idx + 1),
+                    secret,
+                    32 * idx + 16,
+                    seed,
+                ));
+            idx += 1;
+        }
+        avalanche(acc)
+    } else if len <= 240 {
+        let mut acc = (len as u64).wrapping_mul(PRIME64_1);
+        let mut idx = 0;
+        while idx < 8 {
+            acc = acc.wrapping_add(mix16(bytes, 16 * idx, secret, 16 * idx, seed));
+            idx += 1;
+        }
+        acc = avalanche(acc);
+        while idx < len / 16 {
+            acc = acc.wrapping_add(mix16(bytes, 16 * idx, secret, 16 * (idx - 8) + 3, seed));
+            idx += 1;
+        }
+        acc = acc.wrapping_add(mix16(bytes, len - 16, secret, 136 - 17, seed));
+        avalanche(acc)
+    } else if seed == 0 {
+        xxh3_long(bytes, secret)
+    } else {
+        xxh3_long(bytes, &custom_secret(seed))
+    }
+}
+
+const fn xxh3_long(bytes: &[u8], secret: &[u8; SECRET_SIZE]) -> u64 {
+    let len = bytes.len();
+    let mut acc = [
+        PRIME32_3, PRIME64_1, PRIME64_2, PRIME64_3, PRIME64_4, PRIME32_2, PRIME64_5, PRIME32_1,
+    ];
+    let blocks = (len - 1) / BLOCK_LEN;
+    let mut block = 0;
+    while block < blocks {
+        acc = accumulate(acc, bytes, block * BLOCK_LEN, secret, STRIPES_PER_BLOCK);
+        acc = scramble(acc, secret, SECRET_SIZE - STRIPE_LEN);
+        block += 1;
+    }
+    let stripes = ((len - 1) - BLOCK_LEN * blocks) / STRIPE_LEN;
+    acc = accumulate(acc, bytes, blocks * BLOCK_LEN, secret, stripes);
+    acc = accumulate_512(
+        acc,
+        bytes,
+        len - STRIPE_LEN,
+        secret,
+        SECRET_SIZE - STRIPE_LEN - 7,
+    );
+
+    let mut result = (len as u64).wrapping_mul(PRIME64_1);
+    let mut idx = 0;
+    while idx < 4 {
+        result = result.wrapping_add(mul128_fold64(
+            acc[2 * idx] ^ read_u64(secret, 11 + 16 * idx),
+            acc[2 * idx + 1] ^ read_u64(secret, 11 + 16 * idx + 8),
+        ));
+        idx += 1;
+    }
+    avalanche(result)
+}
+
+const fn accumulate(
+    mut acc: [u64; 8],
+    bytes: &[u8],
+    offset: usize,
+    secret: &[u8; SECRET_SIZE],
+    stripes: usize,
+) -> [u64; 8] {
+    let mut stripe = 0;
+    while stripe < stripes {
+        acc = accumulate_512(
+            acc,
+            bytes,
+            offset + stripe * STRIPE_LEN,
+            secret,
+            stripe * SECRET_CONSUME_RATE,
+        );
+        stripe += 1;
+    }
+    acc
+}
+
+const fn accumulate_512(
+    mut acc: [u64; 8],
+    bytes: &[u8],
+    offset: usize,
+    secret: &[u8; SECRET_SIZE],
+    secret_offset: usize,
+) -> [u64; 8] {
+    let mut idx = 0;
+    while idx < 8 {
+        let value = read_u64(bytes, offset + 8 * idx);
+        let key = value ^ read_u64(secret, secret_offset + 8 * idx);
+        acc[idx ^ 1] = acc[idx ^ 1].wrapping_add(value);
+        acc[idx] = acc[idx].wrapping_add((key & 0xffff_ffff).wrapping_mul(key >> 32));
+        idx += 1;
+    }
+    acc
+}
+
+const fn scramble(mut acc: [u64; 8], secret: &[u8; SECRET_SIZE], offset: usize) -> [u64; 8] {
+    let mut idx = 0;
+    while idx < 8 {
+        let mut a = acc[idx];
+        a ^= a >> 47;
+        a ^= read_u64(secret, offset + 8 * idx);
+        acc[idx] = a.wrapping_mul(PRIME32_1);
+        idx += 1;
+    }
+    acc
+}
+
+const fn custom_secret(seed: u64) -> [u8; SECRET_SIZE] {
+    let mut secret = [0; SECRET_SIZE];
+    let mut idx = 0;
+    while idx < SECRET_SIZE / 16 {
+        let lo = read_u64(&DEFAULT_SECRET, 16 * idx).wrapping_add(seed);
+        let hi = read_u64(&DEFAULT_SECRET, 16 * idx + 8).wrapping_sub(seed);
+        let (lo, hi) = (lo.to_le_bytes(), hi.to_le_bytes());
+        let mut byte = 0;
+        while byte < 8 {
+            secret[16 * idx + byte] = lo[byte];
+            secret[16 * idx + 8 + byte] = hi[byte];
+            byte += 1;
+        }
+        idx += 1;
+    }
+    secret
+}
+
+const fn mix16(bytes: &[u8], offset: usize, secret: &[u8], secret_offset: usize, seed: u64) -> u64 {
+    mul128_fold64(
+        read_u64(bytes, offset) ^ read_u64(secret, secret_offset).wrapping_add(seed),
+        read_u64(bytes, offset + 8) ^ read_u64(secret, secret_offset + 8).wrapping_sub(seed),
+    )
+}
+
+const fn mul128_fold64(lhs: u64, rhs: u64) -> u64 {
+    let product = lhs as u128 * rhs as u128;
+    product as u64 ^ (product >> 64) as u64
+}
+
+const fn xxh64_avalanche(mut h: u64) -> u64 {
+    h ^= h >> 33;
+    h = h.wrapping_mul(PRIME64_2);
+    h ^= h >> 29;
+    h = h.wrapping_mul(PRIME64_3);
+    h ^ (h >> 32)
+}
+
+const fn avalanche(mut h: u64) -> u64 {
+    h ^= h >> 37;
+    h = h.wrapping_mul(PRIME_MX1);
+    h ^ (h >> 32)
+}
+
+const fn rrmxmx(mut h: u64, len: u64) -> u64 {
+    h ^= h.rotate_left(49) ^ h.rotate_left(24);
+    h = h.wrapping_mul(PRIME_MX2);
+    h ^= (h >> 35).wrapping_add(len);
+    h = h.wrapping_mul(PRIME_MX2);
+    h ^ (h >> 28)
+}
+
+impl<const N: usize> KStr<N> {
+    /// Returns the 32 bits FNV-1a hash of the String, see [`fnv1a_32`].
+    pub const fn fnv1a_32(&self) -> u32 {
+        fnv1a_32(self.as_bytes())
+    }
+    /// Returns the 64 bits FNV-1a hash of the String, see [`fnv1a_64`].
+    pub const fn fnv1a_64(&self) -> u64 {
+        fnv1a_64(self.as_bytes())
+    }
+    /// Returns the CRC-32 checksum of the String, see [`crc32`].
+    pub const fn crc32(&self) -> u32 {
+        crc32(self.as_bytes())
+    }
+    /// Returns the 32 bits MurmurHash3 of the String, see [`murmur3_32`].
+    pub const fn murmur3_32(&self, seed: u32) -> u32 {
+        murmur3_32(self.as_bytes(), seed)
+    }
+    /// Returns the 128 bits MurmurHash3 of the String, see [`murmur3_x64_128`].
+    pub const fn murmur3_x64_128(&self, seed: u32) -> u128 {
+        murmur3_x64_128(self.as_bytes(), seed)
+    }
+    /// Returns the 64 bits XXH3 hash of the String, see [`xxh3_64`].
+    pub const fn xxh3_64(&self) -> u64 {
+        xxh3_64(self.as_bytes())
+    }
+    /// Returns the 64 bits XXH3 hash with a seed of the String, see [`xxh3_64_with_seed`].
+    pub const fn xxh3_64_with_seed(&self, seed: u64) -> u64 {
+        xxh3_64_with_seed(self.as_bytes(), seed)
+    }
+}
+
+impl<const N: usize> KVec<u8, N> {
+    /// Returns the 32 bits FNV-1a hash of the Vector, see [`fnv1a_32`].
+    pub const fn fnv1a_32(&self) -> u32 {
+        fnv1a_32(self.as_slice())
+    }
+    /// Returns the 64 bits FNV-1a hash of the Vector, see [`fnv1a_64`].
+    pub const fn fnv1a_64(&self) -> u64 {
+        fnv1a_64(self.as_slice())
+    }
+    /// Returns the CRC-32 checksum of the Vector, see [`crc32`].
+    pub const fn crc32(&self) -> u32 {
+        crc32(self.as_slice())
+    }
+    /// Returns the 32 bits MurmurHash3 of the Vector, see [`murmur3_32`].
+    pub const fn murmur3_32(&self, seed: u32) -> u32 {
+        murmur3_32(self.as_slice(), seed)
+    }
+    /// Returns the 128 bits MurmurHash3 of the Vector, see [`murmur3_x64_128`].
+    pub const fn murmur3_x64_128(&self, seed: u32) -> u128 {
+        murmur3_x64_128(self.as_slice(), seed)
+    }
+    /// Returns the 64 bits XXH3 hash of the Vector, see [`xxh3_64`].
+    pub const fn xxh3_64(&self) -> u64 {
+        xxh3_64(self.as_slice())
+    }
+    /// Returns the 64 bits XXH3 hash with a seed of the Vector, see [`xxh3_64_with_seed`].
+    pub const fn xxh3_64_with_seed(&self, seed: u64) -> u64 {
+        xxh3_64_with_seed(self.as_slice(), seed)
+    }
+}
+
+#[cfg(test)]
+mod hash_test {
+    use super::*;
+    use crate::encoding::hex_decode;
+
+    const FOX: &[u8] = b"The quick brown fox jumps over the lazy dog";
+    const _: () = {
+        assert!(fnv1a_32(b"a") == 0xe40c_292c);
+        assert!(KStr::<8>::from_str("a").fnv1a_64() == 0xaf63_dc4c_8601_ec8c);
+        let bytes: KVec<u8, 4> = match hex_decode("61") {
+            Ok(bytes) => bytes,
+            Err(err) => err.panic(),
+        };
+        assert!(bytes.crc32() == 0xe8b7_be43);
+        assert!(murmur3_32(b"", 1) == 0x514e_28b7);
+        assert!(xxh3_64(b"") == 0x2d06_8005_38d3_94c2);
+    };
+
+    /// Deterministic pseudo random bytes, long enough to reach every XXH3 code path.
+    fn data(len: usize) -> Vec<u8> {
+        (0..len as u32)
+            .map(|idx| (idx.wrapping_mul(2_654_435_761) >> 13) as u8)
+            .collect()
+    }
+
+    #[test]
+    fn reference_vectors() {
+        for (input, fnv32, fnv64, crc, m32, m128) in [
+            (&b""[..], 0x811c_9dc5, 0xcbf2_9ce4_8422_2325, 0, 0, 0),
+            (
+                b"hello world",
+                0xd58b_3fa7,
+                0x779a_65e7_023c_d2e7,
+                0x0d4a_1185,
+                0x5e92_8f0f,
+                0xab97_467d_60eb_63b1_533f_6046_eb7f_610e,
+            ),
+            (
+                FOX,
+                0x048f_ff90,
+                0xf3f9_b7f5_e7e4_7110,
+                0x414f_a339,
+                0x2e4f_f723,
+                0x7a43_3ca9_c49a_9347_e34b_bc7b_bc07_1b6c,
+            ),
+        ] {
+            assert_eq!(fnv1a_32(input), fnv32);
+            assert_eq!(fnv1a_64(input), fnv64);
+            assert_eq!(crc32(input), crc);
+            assert_eq!(murmur3_32(input, 0), m32);
+            assert_eq!(murmur3_x64_128(input, 0), m128);
+        }
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32_update(crc32(&FOX[..10]), &FOX[10..]), crc32(FOX));
+    }
+
+    #[test]
+    fn methods() {
+        let k = KStr::<64>::from_str("hello world");
+        let bytes = k.as_bytes();
+        let v = bytes.iter().fold(KVec::<u8, 64>::new(0), |v, b| v.push(*b));
+        assert_eq!(
+            (k.fnv1a_32(), v.fnv1a_32()),
+            (fnv1a_32(bytes), fnv1a_32(bytes))
+        );
+        assert_eq!(
+            (k.fnv1a_64(), v.fnv1a_64()),
+            (fnv1a_64(bytes), fnv1a_64(bytes))
+        );
+        assert_eq!((k.crc32(), v.crc32()), (crc32(bytes), crc32(bytes)));
+        assert_eq!(k.murmur3_32(7), murmur3_32(bytes, 7));
+        assert_eq!(v.murmur3_x64_128(7), murmur3_x64_128(bytes, 7));
+        assert_eq!(k.xxh3_64(), v.xxh3_64());
+        assert_eq!(v.xxh3_64_with_seed(42), xxh3_64_with_seed(bytes, 42));
+    }
+
+    #[test]
+    fn xxh3_reference_vectors() {
+        assert_eq!(xxh3_64(b"a"), 0xe6c6_32b6_1e96_4e1f);
+        assert_eq!(xxh3_64(FOX), 0xce7d_19a5_418f_b365);
+        for (len, hash, seeded) in [
+            (2, 0x1ca5_cfa6_a6d5_7dc2, 0xba2d_2652_6638_3cc1),
+            (6, 0x3599_2a97_d632_ac96, 0xe266_91ef_4e08_3ed1),
+            (12, 0x69e7_35c8_925c_eff1, 0x2719_4114_db42_2c89),
+            (100, 0xad1e_77ff_670a_2548, 0x45d8_a55b_26b7_81af),
+            (200, 0x20a8_7db9_07ce_74e4, 0xd81c_d65f_6222_738d),
+            (1000, 0xa067_b58e_6ea5_d2f2, 0xe1e9_2898_cea5_5448),
+            (3000, 0x3a23_7995_ed9e_981f, 0x2a11_bb8d_6ef9_4a6c),
+        ] {
+            let input = data(len);
+            assert_eq!(xxh3_64(&input), hash, "len {len}");
+            assert_eq!(xxh3_64_with_seed(&input, 42), seeded, "len {len}");
+        }
+    }
+}
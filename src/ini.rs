@@ -0,0 +1,684 @@
+//! The format is INI with TOML values: `[section]` headers, `key = value` lines, and `#` or `;`
+//! comments. Keys before the first header go in the section named `""`.
+//!
+//! Values are basic strings `"a\tb"` (with JSON escapes), literal strings `'C:\dir'`, integers
+//! like `-12`, `1_000` or `0xff`, `true`/`false`, and single line arrays of those like `[1, 2, 3]`.
+//! Anything else is a bare string running to the end of the line, so comments can only follow
+//! quoted values, integers, booleans and arrays.
+//!
+//! ```rust
+//! use konster::ini::KIni;
+//!
+//! const CONFIG: KIni<4, 8, 16> = KIni::parse(
+//!     "name = konster
+//!     [server]
+//!     port = 8080 # http
+//!     tls = false",
+//! );
+//! const PORT: u32 = match CONFIG.get_u32("server", "port") {
+//!     Some(port) => port,
+//!     None => panic!("server.port is required"),
+//! };
+//! assert_eq!(PORT, 8080);
+//! assert_eq!(CONFIG.get_str("", "name"), Some("konster"));
+//! ```
+use crate::error::impl_error;
+use crate::kmap::KMap;
+use crate::kstr::{
+    bytes_eq, char_at, skip_spaces, split_once, trim_end, trim_start, unescape_json,
+    EscapeErrorKind, KStr, Pattern, SplitCursor,
+};
+use crate::kvec::KVec;
+
+/// Error returned when parsing a malformed config
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IniError {
+    /// What is wrong with the config
+    pub kind: IniErrorKind,
+    /// Line of the error, starting at 1
+    pub line: usize,
+    /// Column of the error in chars, starting at 1
+    pub column: usize,
+}
+
+/// The different ways a config can be malformed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IniErrorKind {
+    /// A section header that is not closed or whose name is not a valid key
+    InvalidSection,
+    /// A section header seen twice
+    DuplicateSection,
+    /// A key that is empty or has chars other than ASCII alphanumerics, `_`, `-` and `.`
+    InvalidKey,
+    /// A line that is neither a header nor a `key = value` pair
+    MissingEquals,
+    /// A key set twice in the same section
+    DuplicateKey,
+    /// A key without a value
+    MissingValue,
+    /// A quoted string without its closing quote
+    UnterminatedString,
+    /// A basic string with a malformed escape
+    InvalidEscape(EscapeErrorKind),
+    /// An integer that doesn't fit in an `i64`
+    InvalidInteger,
+    /// An array that is not closed or has an item that is not a quoted string, an integer or a boolean
+    InvalidArray,
+    /// Something other than a comment after a value or a header
+    TrailingChars,
+}
+
+impl IniErrorKind {
+    /// Returns a description of the error.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            IniErrorKind::InvalidSection => "invalid section header",
+            IniErrorKind::DuplicateSection => "duplicate section",
+            IniErrorKind::InvalidKey => "invalid key",
+            IniErrorKind::MissingEquals => "missing `=` after key",
+            IniErrorKind::DuplicateKey => "duplicate key",
+            IniErrorKind::MissingValue => "missing value",
+            IniErrorKind::UnterminatedString => "unterminated string",
+            IniErrorKind::InvalidEscape(kind) => kind.as_str(),
+            IniErrorKind::InvalidInteger => "integer out of range",
+            IniErrorKind::InvalidArray => "invalid array",
+            IniErrorKind::TrailingChars => "unexpected chars after value",
+        }
+    }
+}
+
+impl IniError {
+    /// Builds the error for the byte index `offset` of the line.
+    const fn new(kind: IniErrorKind, line: usize, text: &str, offset: usize) -> Self {
+        let mut column = 1;
+        let mut idx = 0;
+        while idx < offset {
+            match char_at(text, idx) {
+                Some((_, next)) => idx = next,
+                None => break,
+            }
+            column += 1;
+        }
+        Self { kind, line, column }
+    }
+}
+impl_error!(IniError, " at line " line, " column " column);
+
+/// A config value, Strings have a capacity of `S`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IniValue<const S: usize> {
+    /// A quoted or bare string
+    String(KStr<S>),
+    /// An integer
+    Integer(i64),
+    /// `true` or `false`
+    Bool(bool),
+    /// An array as written in the config, use [`KIni::get_array`] to read its items
+    Array(KStr<S>),
+}
+
+impl<const S: usize> IniValue<S> {
+    /// Returns the string, or None if the value is not a string
+    pub const fn as_str(&self) -> Option<&str> {
+        match self {
+            IniValue::String(s) => Some(s.as_str()),
+            _ => None,
+        }
+    }
+    /// Returns the integer, or None if the value is not an integer
+    pub const fn as_i64(&self) -> Option<i64> {
+        match self {
+            IniValue::Integer(n) => Some(*n),
+            _ => None,
+        }
+    }
+    /// Returns the boolean, or None if the value is not a boolean
+    pub const fn as_bool(&self) -> Option<bool> {
+        match self {
+            IniValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+}
+
+/// Keys and values of a section
+pub type IniSection<const K: usize, const S: usize> = KMap<KStr<S>, IniValue<S>, K>;
+
+/// A parsed config of at most `N` sections of at most `K` keys, whose names and values fit in `S` bytes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KIni<const N: usize, const K: usize, const S: usize> {
+    /// The sections in the order of the config, with their keys
+    pub sections: KMap<KStr<S>, IniSection<K, S>, N>,
+}
+
+impl<const N: usize, const K: usize, const S: usize> KIni<N, K, S> {
+    /// Parses a config.
+    /// # Panics: panic if the config is malformed, doesn't fit in `N` sections of `K` keys,
+    /// or a String doesn't fit in `S`
+    pub const fn parse(s: &str) -> Self {
+        match Self::try_parse(s) {
+            Ok(ini) => ini,
+            Err(err) => err.panic(),
+        }
+    }
+    /// Parses a config, or returns where it is malformed.
+    /// # Panics: panic if the config doesn't fit in `N` sections of `K` keys, or a String doesn't fit in `S`
+    pub const fn try_parse(s: &str) -> Result<Self, IniError> {
+        let mut ini = Self {
+            sections: KMap {
+                vec: KVec::new((KStr::new(), empty_section())),
+            },
+        };
+        let mut lines = SplitCursor::lines(s);
+        let mut line_no = 0;
+        while let Some((next, line)) = lines.next() {
+            lines = next;
+            line_no += 1;
+            let content = trim_start(line);
+            let indent = line.len() - content.len();
+            let result = match content.as_bytes() {
+                [] | [b'#' | b';', ..] => Ok(()),
+                [b'[', ..] => ini.header(content),
+                _ => ini.key_value(content),
+            };
+            if let Err((kind, offset)) = result {
+                return Err(IniError::new(kind, line_no, line, indent + offset));
+            }
+        }
+        Ok(ini)
+    }
+    /// Returns the keys of the section
+    pub const fn section(&self, name: &str) -> Option<&IniSection<K, S>> {
+        match self.section_idx(name) {
+            Some(idx) => Some(&self.sections.vec.buf[idx].1),
+            None => None,
+        }
+    }
+    /// Returns the value of the key in the section, use `""` for the keys before the first section
+    pub const fn get(&self, section: &str, key: &str) -> Option<&IniValue<S>> {
+        let section = match self.section(section) {
+            Some(section) => section,
+            None => return None,
+        };
+        match key_idx(section, key) {
+            Some(idx) => Some(&section.vec.buf[idx].1),
+            None => None,
+        }
+    }
+    /// Returns the string value of the key, or None if it is missing or not a string
+    pub const fn get_str(&self, section: &str, key: &str) -> Option<&str> {
+        match self.get(section, key) {
+            Some(value) => value.as_str(),
+            None => None,
+        }
+    }
+    /// Returns the integer value of the key, or None if it is missing or not an integer
+    pub const fn get_i64(&self, section: &str, key: &str) -> Option<i64> {
+        match self.get(section, key) {
+            Some(value) => value.as_i64(),
+            None => None,
+        }
+    }
+    /// Returns the integer value of the key, or None if it is missing or not an integer that fits in a `u32`
+    pub const fn get_u32(&self, section: &str, key: &str) -> Option<u32> {
+        match self.get_i64(section, key) {
+            Some(n) if n >= 0 && n <= u32::MAX as i64 => Some(n as u32),
+            _ => None,
+        }
+    }
+    /// Returns the boolean value of the key, or None if it is missing or not a boolean
+    pub const fn get_bool(&self, section: &str, key: &str) -> Option<bool> {
+        match self.get(section, key) {
+            Some(value) => value.as_bool(),
+            None => None,
+        }
+    }
+    /// Returns the items of the array value of the key in a Vector of capacity `L`,
+    /// or None if it is missing or not an array.
+    /// # Panics: panic if the items don't fit in `L`
+    pub const fn get_array<const L: usize>(
+        &self,
+        section: &str,
+        key: &str,
+    ) -> Option<KVec<IniValue<S>, L>> {
+        let raw = match self.get(section, key) {
+            Some(IniValue::Array(raw)) => raw.as_str(),
+            _ => return None,
+        };
+        let mut items = KVec::new(IniValue::Bool(false));
+        let mut pos = 1;
+        // The array was checked when parsing
+        while let Ok((Some(item), next)) = array_item(raw, pos) {
+            items = items.push(item);
+            pos = next;
+        }
+        Some(items)
+    }
+    const fn section_idx(&self, name: &str) -> Option<usize> {
+        let mut idx = 0;
+        while idx < self.sections.len() {
+            if bytes_eq(self.sections.vec.buf[idx].0.as_bytes(), name.as_bytes()) {
+                return Some(idx);
+            }
+            idx += 1;
+        }
+        None
+    }
+    const fn push_section(&mut self, name: &str) {
+        self.sections.vec = self
+            .sections
+            .vec
+            .push((KStr::new().extend(name.as_bytes()), empty_section()));
+    }
+    /// Parses a header line, errors hold the byte index in the line
+    const fn header(&mut self, line: &str) -> Result<(), (IniErrorKind, usize)> {
+        let (name, rest) = match split_once(line.split_at(1).1, Pattern::byte(b']')) {
+            Some(found) => found,
+            None => return Err((IniErrorKind::InvalidSection, 0)),
+        };
+        let name = trim_end(trim_start(name));
+        if !is_key(name) {
+            return Err((IniErrorKind::InvalidSection, 0));
+        }
+        if let Err(offset) = check_trailing(rest) {
+            return Err((
+                IniErrorKind::TrailingChars,
+                line.len() - rest.len() + offset,
+            ));
+        }
+        if self.section_idx(name).is_some() {
+            return Err((IniErrorKind::DuplicateSection, 0));
+        }
+        self.push_section(name);
+        Ok(())
+    }
+    /// Parses a `key = value` line, errors hold the byte index in the line
+    const fn key_value(&mut self, line: &str) -> Result<(), (IniErrorKind, usize)> {
+        let (key, raw) = match split_once(line, Pattern::byte(b'=')) {
+            Some(found) => found,
+            None => return Err((IniErrorKind::MissingEquals, line.len())),
+        };
+        let key = trim_end(key);
+        if !is_key(key) {
+            return Err((IniErrorKind::InvalidKey, 0));
+        }
+        let value_start = line.len() - trim_start(raw).len();
+        let raw = trim_end(trim_start(raw));
+        if raw.is_empty() {
+            return Err((IniErrorKind::MissingValue, value_start));
+        }
+        let value = match value::<S>(raw) {
+            Ok((value, end)) => match check_trailing(raw.split_at(end).1) {
+                Ok(()) => value,
+                Err(offset) => {
+                    return Err((IniErrorKind::TrailingChars, value_start + end + offset))
+                }
+            },
+            Err((kind, offset)) => return Err((kind, value_start + offset)),
+        };
+        if self.sections.is_empty() {
+            self.push_section("");
+        }
+        let section = &mut self.sections.vec.buf[self.sections.vec.cursor - 1].1;
+        if key_idx(section, key).is_some() {
+            return Err((IniErrorKind::DuplicateKey, 0));
+        }
+        section.vec = section
+            .vec
+            .push((KStr::new().extend(key.as_bytes()), value));
+        Ok(())
+    }
+}
+
+const fn empty_section<const K: usize, const S: usize>() -> IniSection<K, S> {
+    KMap {
+        vec: KVec::new((KStr::new(), IniValue::Bool(false))),
+    }
+}
+
+const fn key_idx<const K: usize, const S: usize>(
+    section: &IniSection<K, S>,
+    key: &str,
+) -> Option<usize> {
+    let mut idx = 0;
+    while idx < section.len() {
+        if bytes_eq(section.vec.buf[idx].0.as_bytes(), key.as_bytes()) {
+            return Some(idx);
+        }
+        idx += 1;
+    }
+    None
+}
+
+const fn is_key(key: &str) -> bool {
+    let bytes = key.as_bytes();
+    let mut idx = 0;
+    while idx < bytes.len() {
+        if !matches!(bytes[idx], b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'_' | b'-' | b'.') {
+            return false;
+        }
+        idx += 1;
+    }
+    !bytes.is_empty()
+}
+
+/// Checks that only spaces or a comment follow a value, or returns the index of the first offending char.
+const fn check_trailing(rest: &str) -> Result<(), usize> {
+    let trimmed = trim_start(rest);
+    match trimmed.as_bytes() {
+        [] | [b'#' | b';', ..] => Ok(()),
+        _ => Err(rest.len() - trimmed.len()),
+    }
+}
+
+/// Parses a value, returns it with the index after it, or the error with its index.
+const fn value<const S: usize>(raw: &str) -> Result<(IniValue<S>, usize), (IniErrorKind, usize)> {
+    match raw.as_bytes()[0] {
+        b'"' | b'\'' => quoted(raw, 0),
+        b'[' => {
+            let mut pos = 1;
+            loop {
+                match array_item::<S>(raw, pos) {
+                    Ok((Some(_), next)) => pos = next,
+                    Ok((None, end)) => {
+                        pos = end;
+                        break;
+                    }
+                    Err(err) => return Err(err),
+                }
+            }
+            let array = raw.split_at(pos).0;
+            Ok((IniValue::Array(KStr::new().extend(array.as_bytes())), pos))
+        }
+        _ => {
+            // An integer or a boolean can be followed by a comment, a bare string can not
+            let bytes = raw.as_bytes();
+            let mut end = 0;
+            while end < bytes.len() && !matches!(bytes[end], b' ' | b'\t') {
+                end += 1;
+            }
+            match scalar(raw.split_at(end).0) {
+                Ok(Some(value)) if check_trailing(raw.split_at(end).1).is_ok() => Ok((value, end)),
+                Err(kind) => Err((kind, 0)),
+                _ => Ok((
+                    IniValue::String(KStr::new().extend(raw.as_bytes())),
+                    raw.len(),
+                )),
+            }
+        }
+    }
+}
+
+/// Parses the quoted string starting at `pos`.
+const fn quoted<const S: usize>(
+    raw: &str,
+    pos: usize,
+) -> Result<(IniValue<S>, usize), (IniErrorKind, usize)> {
+    let bytes = raw.as_bytes();
+    let quote = bytes[pos];
+    let mut end = pos + 1;
+    while end < bytes.len() && bytes[end] != quote {
+        end += if quote == b'"' && bytes[end] == b'\\' {
+            2
+        } else {
+            1
+        };
+    }
+    if end >= bytes.len() {
+        return Err((IniErrorKind::UnterminatedString, pos));
+    }
+    let inner = raw.split_at(end).0.split_at(pos + 1).1;
+    if quote == b'\'' {
+        return Ok((
+            IniValue::String(KStr::new().extend(inner.as_bytes())),
+            end + 1,
+        ));
+    }
+    match unescape_json(inner) {
+        Ok(s) => Ok((IniValue::String(s), end + 1)),
+        Err(err) => Err((IniErrorKind::InvalidEscape(err.kind), pos + 1 + err.offset)),
+    }
+}
+
+/// Parses the array item at `pos`, 1 for the first one or the index after the previous one.
+/// Returns the item and the index after it, or None and the index after the closing bracket.
+const fn array_item<const S: usize>(
+    raw: &str,
+    pos: usize,
+) -> Result<(Option<IniValue<S>>, usize), (IniErrorKind, usize)> {
+    let bytes = raw.as_bytes();
+    let first = pos == 1;
+    let mut pos = skip_spaces(raw, pos);
+    if !first {
+        // The previous item is followed by a comma or the closing bracket
+        if bytes[pos] == b']' {
+            return Ok((None, pos + 1));
+        }
+        pos = skip_spaces(raw, pos + 1);
+    }
+    if pos < bytes.len() && bytes[pos] == b']' {
+        return Ok((None, pos + 1));
+    }
+    if pos == bytes.len() {
+        return Err((IniErrorKind::InvalidArray, pos));
+    }
+    let (item, end) = if matches!(bytes[pos], b'"' | b'\'') {
+        match quoted(raw, pos) {
+            Ok(found) => found,
+            Err(err) => return Err(err),
+        }
+    } else {
+        let mut end = pos;
+        while end < bytes.len() && !matches!(bytes[end], b',' | b']' | b' ' | b'\t') {
+            end += 1;
+        }
+        match scalar(raw.split_at(end).0.split_at(pos).1) {
+            Ok(Some(value)) => (value, end),
+            Ok(None) => return Err((IniErrorKind::InvalidArray, pos)),
+            Err(kind) => return Err((kind, pos)),
+        }
+    };
+    let next = skip_spaces(raw, end);
+    if next == bytes.len() || !matches!(bytes[next], b',' | b']') {
+        return Err((IniErrorKind::InvalidArray, next));
+    }
+    Ok((Some(item), next))
+}
+
+/// Parses an integer or a boolean, returns None for other words.
+const fn scalar<const S: usize>(word: &str) -> Result<Option<IniValue<S>>, IniErrorKind> {
+    match word.as_bytes() {
+        b"true" => return Ok(Some(IniValue::Bool(true))),
+        b"false" => return Ok(Some(IniValue::Bool(false))),
+        _ => {}
+    }
+    match parse_integer(word) {
+        Ok(Some(n)) => Ok(Some(IniValue::Integer(n))),
+        Ok(None) => Ok(None),
+        Err(()) => Err(IniErrorKind::InvalidInteger),
+    }
+}
+
+/// Parses a TOML integer, returns None if the word is not one and an error if it overflows.
+const fn parse_integer(word: &str) -> Result<Option<i64>, ()> {
+    let (negative, digits, radix) = match word.as_bytes() {
+        [b'0', b'x', rest @ ..] => (false, rest, 16),
+        [b'0', b'o', rest @ ..] => (false, rest, 8),
+        [b'0', b'b', rest @ ..] => (false, rest, 2),
+        [b'-', rest @ ..] => (true, rest, 10),
+        [b'+', rest @ ..] => (false, rest, 10),
+        rest => (false, rest, 10),
+    };
+    // Decimal integers don't have leading zeros, and `_` must be between digits
+    if digits.is_empty()
+        || (radix == 10 && digits.len() > 1 && digits[0] == b'0')
+        || digits[0] == b'_'
+        || digits[digits.len() - 1] == b'_'
+    {
+        return Ok(None);
+    }
+    let mut value: u64 = 0;
+    let mut overflow = false;
+    let mut idx = 0;
+    while idx < digits.len() {
+        let digit = match digits[idx] {
+            b'_' if digits[idx - 1] != b'_' => {
+                idx += 1;
+                continue;
+            }
+            b @ b'0'..=b'9' => (b - b'0') as u64,
+            b @ b'a'..=b'f' => (b - b'a' + 10) as u64,
+            b @ b'A'..=b'F' => (b - b'A' + 10) as u64,
+            _ => return Ok(None),
+        };
+        if digit >= radix {
+            return Ok(None);
+        }
+        match value.checked_mul(radix) {
+            Some(n) => match n.checked_add(digit) {
+                Some(n) => value = n,
+                None => overflow = true,
+            },
+            None => overflow = true,
+        }
+        idx += 1;
+    }
+    if overflow {
+        return Err(());
+    }
+    if negative {
+        if value > i64::MIN.unsigned_abs() {
+            return Err(());
+        }
+        Ok(Some(0u64.wrapping_sub(value) as i64))
+    } else if value > i64::MAX as u64 {
+        Err(())
+    } else {
+        Ok(Some(value as i64))
+    }
+}
+
+#[cfg(test)]
+mod ini_test {
+    use super::*;
+
+    const CONFIG: KIni<4, 8, 32> = KIni::parse(
+        "# global settings
+        name = konster app
+        debug = true
+
+        [server]
+        host = \"local\\thost\" ; quoted
+        port = 8_080
+        mask = 0xff
+        paths = ['/a', \"/b\", ]
+        weights = [1, -2,3]
+
+        [server.tls]
+        enabled=false
+        cert = 'C:\\certs\\a.pem' # literal
+        offset = -9223372036854775808",
+    );
+    const _: () = {
+        assert!(matches!(CONFIG.get_u32("server", "port"), Some(8080)));
+        assert!(matches!(
+            CONFIG.get_bool("server.tls", "enabled"),
+            Some(false)
+        ));
+        assert!(CONFIG.get_u32("server.tls", "offset").is_none());
+    };
+
+    #[test]
+    fn getters() {
+        assert_eq!(CONFIG.sections.len(), 3);
+        assert_eq!(CONFIG.get_str("", "name"), Some("konster app"));
+        assert_eq!(CONFIG.get_bool("", "debug"), Some(true));
+        assert_eq!(CONFIG.get_str("server", "host"), Some("local\thost"));
+        assert_eq!(CONFIG.get_i64("server", "mask"), Some(255));
+        assert_eq!(
+            CONFIG.get_str("server.tls", "cert"),
+            Some(r"C:\certs\a.pem")
+        );
+        assert_eq!(CONFIG.get_i64("server.tls", "offset"), Some(i64::MIN));
+        assert_eq!(CONFIG.get_u32("server", "host"), None);
+        assert_eq!(CONFIG.get_u32("missing", "port"), None);
+        assert_eq!(CONFIG.section("server").map(|s| s.len()), Some(5));
+
+        let paths = CONFIG.get_array::<4>("server", "paths").unwrap();
+        let paths: Vec<_> = paths.as_slice().iter().map(|p| p.as_str()).collect();
+        assert_eq!(paths, [Some("/a"), Some("/b")]);
+        let weights = CONFIG.get_array::<4>("server", "weights").unwrap();
+        let weights: Vec<_> = weights.as_slice().iter().map(|w| w.as_i64()).collect();
+        assert_eq!(weights, [Some(1), Some(-2), Some(3)]);
+        assert!(CONFIG.get_array::<4>("server", "port").is_none());
+    }
+
+    #[test]
+    fn values() {
+        for (raw, expected) in [
+            ("0", IniValue::Integer(0)),
+            ("+1_000", IniValue::Integer(1000)),
+            ("0o17", IniValue::Integer(15)),
+            ("0b101", IniValue::Integer(5)),
+            ("01", IniValue::String(KStr::from_str("01"))),
+            ("1__0", IniValue::String(KStr::from_str("1__0"))),
+            ("1.5", IniValue::String(KStr::from_str("1.5"))),
+            ("#fff", IniValue::String(KStr::from_str("#fff"))),
+            ("true # yes", IniValue::Bool(true)),
+            ("true story", IniValue::String(KStr::from_str("true story"))),
+            ("1 2", IniValue::String(KStr::from_str("1 2"))),
+            ("\"\"", IniValue::String(KStr::new())),
+            ("[]", IniValue::Array(KStr::from_str("[]"))),
+        ] {
+            let ini = KIni::<1, 1, 16>::parse(&format!("key = {raw}"));
+            assert_eq!(ini.get("", "key"), Some(&expected), "{raw}");
+        }
+    }
+
+    #[test]
+    fn errors() {
+        for (config, kind, line, column) in [
+            ("[server\nport = 1", IniErrorKind::InvalidSection, 1, 1),
+            ("[a b]", IniErrorKind::InvalidSection, 1, 1),
+            ("[a]\n[b]\n [a]", IniErrorKind::DuplicateSection, 3, 2),
+            ("[a] x", IniErrorKind::TrailingChars, 1, 5),
+            ("key value", IniErrorKind::MissingEquals, 1, 10),
+            ("k y = 1", IniErrorKind::InvalidKey, 1, 1),
+            ("= 1", IniErrorKind::InvalidKey, 1, 1),
+            ("a = 1\n[s]\na = 1\na = 2", IniErrorKind::DuplicateKey, 4, 1),
+            ("a =  ", IniErrorKind::MissingValue, 1, 6),
+            ("a = \"abc", IniErrorKind::UnterminatedString, 1, 5),
+            (
+                "a = 1\nb = \"é\\q\"",
+                IniErrorKind::InvalidEscape(EscapeErrorKind::UnknownEscape),
+                2,
+                7,
+            ),
+            (
+                "a = 9223372036854775808",
+                IniErrorKind::InvalidInteger,
+                1,
+                5,
+            ),
+            ("a = [1, 2", IniErrorKind::InvalidArray, 1, 10),
+            ("a = [1 2]", IniErrorKind::InvalidArray, 1, 8),
+            ("a = [,1]", IniErrorKind::InvalidArray, 1, 6),
+            ("a = [x]", IniErrorKind::InvalidArray, 1, 6),
+            ("a = [[1]]", IniErrorKind::InvalidArray, 1, 6),
+            ("a = 'x' y", IniErrorKind::TrailingChars, 1, 9),
+        ] {
+            let err = KIni::<4, 4, 16>::try_parse(config).unwrap_err();
+            assert_eq!(
+                (err.kind, err.line, err.column),
+                (kind, line, column),
+                "{config:?}"
+            );
+        }
+        assert_eq!(
+            KIni::<1, 1, 4>::try_parse("a = 1\na = 2")
+                .unwrap_err()
+                .to_string(),
+            "duplicate key at line 2 column 1"
+        );
+    }
+}
@@ -0,0 +1,628 @@
+//! The document is stored in fixed capacity arenas: a Vector of at most `N` values, the root being
+//! the first one, and a Map of at most `N` object members holding the index of their value.
+//! Strings, keys and numbers are stored in Strings of capacity `S`.
+//!
+//! ```rust
+//! use konster::json::KJson;
+//!
+//! const CONFIG: KJson<16, 16> = KJson::parse(r#"{"server": {"ports": [80, 443]}}"#);
+//! const HTTPS: Option<u64> = match CONFIG.get("server.ports[1]") {
+//!     Some(port) => port.as_u64(),
+//!     None => None,
+//! };
+//! assert_eq!(HTTPS, Some(443));
+//! ```
+use crate::error::impl_error;
+use crate::kmap::KMap;
+use crate::kstr::{bytes_eq, char_at, parse_i64, parse_u64, unescape_json, EscapeErrorKind, KStr};
+use crate::kvec::KVec;
+
+/// Error returned when parsing malformed JSON
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct JsonError {
+    /// What is wrong with the document
+    pub kind: JsonErrorKind,
+    /// Byte index in the document where the error was found
+    pub offset: usize,
+    /// Line of the error, starting at 1
+    pub line: usize,
+    /// Column of the error in chars, starting at 1
+    pub column: usize,
+}
+
+/// The different ways a JSON document can be malformed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JsonErrorKind {
+    /// A char that can not start or continue a value here
+    UnexpectedChar(char),
+    /// The document ends in the middle of a value
+    UnexpectedEnd,
+    /// A number that doesn't follow the JSON grammar, like `01.` or `-`
+    InvalidNumber,
+    /// A string with a malformed escape or an unescaped control char
+    InvalidString(EscapeErrorKind),
+    /// An object with the same key twice
+    DuplicateKey,
+}
+
+impl JsonErrorKind {
+    /// Returns a description of the error.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            JsonErrorKind::UnexpectedChar(_) => "unexpected char",
+            JsonErrorKind::UnexpectedEnd => "unexpected end",
+            JsonErrorKind::InvalidNumber => "invalid number",
+            JsonErrorKind::InvalidString(kind) => kind.as_str(),
+            JsonErrorKind::DuplicateKey => "duplicate key",
+        }
+    }
+}
+
+impl JsonError {
+    const fn new(kind: JsonErrorKind, s: &str, offset: usize) -> Self {
+        let (mut line, mut column) = (1, 1);
+        let mut idx = 0;
+        while idx < offset {
+            match char_at(s, idx) {
+                Some(('\n', next)) => {
+                    line += 1;
+                    column = 1;
+                    idx = next;
+                }
+                Some((_, next)) => {
+                    column += 1;
+                    idx = next;
+                }
+                None => break,
+            }
+        }
+        Self {
+            kind,
+            offset,
+            line,
+            column,
+        }
+    }
+}
+impl_error!(
+    JsonError char JsonErrorKind::UnexpectedChar,
+    " at line " line,
+    " column " column
+);
+
+/// A JSON value, Strings have a capacity of `S`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JsonValue<const S: usize> {
+    /// `null`
+    Null,
+    /// `true` or `false`
+    Bool(bool),
+    /// A number as written in the document
+    Number(KStr<S>),
+    /// A string with its escapes replaced
+    String(KStr<S>),
+    /// The `len` items are the values starting at index `start`
+    Array {
+        /// Index of the first item in the values
+        start: usize,
+        /// Number of items
+        len: usize,
+    },
+    /// The `len` members are the members starting at index `start`
+    Object {
+        /// Index of the first member in the members
+        start: usize,
+        /// Number of members
+        len: usize,
+    },
+}
+
+impl<const S: usize> JsonValue<S> {
+    /// Returns true if the value is `null`
+    pub const fn is_null(&self) -> bool {
+        matches!(self, JsonValue::Null)
+    }
+    /// Returns the boolean, or None if the value is not a boolean
+    pub const fn as_bool(&self) -> Option<bool> {
+        match self {
+            JsonValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+    /// Returns the string, or None if the value is not a string
+    pub const fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(s) => Some(s.as_str()),
+            _ => None,
+        }
+    }
+    /// Returns the number, or None if the value is not an integer that fits in a `u64`
+    pub const fn as_u64(&self) -> Option<u64> {
+        match self {
+            JsonValue::Number(n) => match parse_u64(n.as_str()) {
+                Ok(value) => Some(value),
+                Err(_) => None,
+            },
+            _ => None,
+        }
+    }
+    /// Returns the number, or None if the value is not an integer that fits in an `i64`
+    pub const fn as_i64(&self) -> Option<i64> {
+        match self {
+            JsonValue::Number(n) => match parse_i64(n.as_str()) {
+                Ok(value) => Some(value),
+                Err(_) => None,
+            },
+            _ => None,
+        }
+    }
+    /// Returns the number, or None if the value is not a number, this one is not const.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            JsonValue::Number(n) => n.as_str().parse().ok(),
+            _ => None,
+        }
+    }
+}
+
+/// A parsed JSON document of at most `N` values and `N` object members,
+/// whose strings, keys and numbers fit in `S` bytes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KJson<const N: usize, const S: usize> {
+    /// The values of the document, the root is the first one
+    pub values: KVec<JsonValue<S>, N>,
+    /// The members of all the objects, with the index of their value
+    pub members: KMap<KStr<S>, usize, N>,
+}
+
+impl<const N: usize, const S: usize> KJson<N, S> {
+    /// Parses a JSON document.
+    /// # Panics: panic if the document is malformed, doesn't fit in `N` values and members,
+    /// or a String doesn't fit in `S`
+    pub const fn parse(s: &str) -> Self {
+        match Self::try_parse(s) {
+            Ok(json) => json,
+            Err(err) => err.panic(),
+        }
+    }
+    /// Parses a JSON document, or returns where it is malformed.
+    /// # Panics: panic if the document doesn't fit in `N` values and members, or a String doesn't fit in `S`
+    pub const fn try_parse(s: &str) -> Result<Self, JsonError> {
+        let mut json = Self {
+            values: KVec::new(JsonValue::Null),
+            members: KMap {
+                vec: KVec::new((KStr::new(), 0)),
+            },
+        };
+        // The slot of the root, the children of each container are moved after it once it is closed
+        json.values = json.values.push(JsonValue::Null);
+        let mut pending = KVec::new((KStr::new(), JsonValue::Null));
+        let end = match json.value(s, skip_ws(s, 0), &mut pending) {
+            Ok((root, end)) => {
+                json.values.buf[0] = root;
+                skip_ws(s, end)
+            }
+            Err(err) => return Err(err),
+        };
+        if end < s.len() {
+            return Err(unexpected(s, end));
+        }
+        Ok(json)
+    }
+    /// Returns the root value
+    pub const fn root(&self) -> &JsonValue<S> {
+        self.values.get_unchecked(0)
+    }
+    /// Returns the item at the index of an array, or None if the value is not an array or is too short
+    pub const fn get_index(&self, array: &JsonValue<S>, idx: usize) -> Option<&JsonValue<S>> {
+        match array {
+            JsonValue::Array { start, len } if idx < *len => self.values.get(*start + idx),
+            _ => None,
+        }
+    }
+    /// Returns the value of the key of an object, or None if the value is not an object or has no such key
+    pub const fn get_key(&self, object: &JsonValue<S>, key: &str) -> Option<&JsonValue<S>> {
+        let (start, len) = match object {
+            JsonValue::Object { start, len } => (*start, *len),
+            _ => return None,
+        };
+        let mut idx = start;
+        while idx < start + len {
+            let (name, value) = self.members.vec.get_unchecked(idx);
+            if bytes_eq(name.as_bytes(), key.as_bytes()) {
+                return self.values.get(*value);
+            }
+            idx += 1;
+        }
+        None
+    }
+    /// Returns the value at a path like `a.b[2]`, made of keys separated by `.` and array indices in brackets.
+    ///
+    /// The empty path is the root, keys containing `.` or `[` can not be reached.
+    pub const fn get(&self, path: &str) -> Option<&JsonValue<S>> {
+        let bytes = path.as_bytes();
+        let mut value = self.root();
+        let mut idx = 0;
+        while idx < bytes.len() {
+            let found = if bytes[idx] == b'[' {
+                let mut end = idx + 1;
+                let mut n: usize = 0;
+                while end < bytes.len() && bytes[end].is_ascii_digit() {
+                    n = match n.checked_mul(10) {
+                        Some(n) => n.saturating_add((bytes[end] - b'0') as usize),
+                        None => return None,
+                    };
+                    end += 1;
+                }
+                if end == idx + 1 || end == bytes.len() || bytes[end] != b']' {
+                    return None;
+                }
+                idx = end + 1;
+                self.get_index(value, n)
+            } else {
+                if idx > 0 {
+                    if bytes[idx] != b'.' {
+                        return None;
+                    }
+                    idx += 1;
+                }
+                let start = idx;
+                while idx < bytes.len() && bytes[idx] != b'.' && bytes[idx] != b'[' {
+                    idx += 1;
+                }
+                let key = path.split_at(idx).0.split_at(start).1;
+                self.get_key(value, key)
+            };
+            value = match found {
+                Some(found) => found,
+                None => return None,
+            };
+        }
+        Some(value)
+    }
+    /// Parses the value starting at `pos`, returns it and the index after it.
+    const fn value(
+        &mut self,
+        s: &str,
+        pos: usize,
+        pending: &mut Pending<N, S>,
+    ) -> Result<(JsonValue<S>, usize), JsonError> {
+        let bytes = s.as_bytes();
+        if pos == bytes.len() {
+            return Err(unexpected(s, pos));
+        }
+        match bytes[pos] {
+            b'[' | b'{' => self.container(s, pos, pending),
+            b'"' => match string::<S>(s, pos) {
+                Ok((string, end)) => Ok((JsonValue::String(string), end)),
+                Err(err) => Err(err),
+            },
+            b'-' | b'0'..=b'9' => match number_end(s, pos) {
+                Ok(end) => Ok((
+                    JsonValue::Number(KStr::new().extend(bytes.split_at(end).0.split_at(pos).1)),
+                    end,
+                )),
+                Err(err) => Err(err),
+            },
+            _ => literal::<S>(s, pos),
+        }
+    }
+    /// Parses the array or object starting at `pos`, returns it and the index after it.
+    ///
+    /// The children are parsed onto `pending`, and moved to contiguous values and members once the container is closed.
+    const fn container(
+        &mut self,
+        s: &str,
+        pos: usize,
+        pending: &mut Pending<N, S>,
+    ) -> Result<(JsonValue<S>, usize), JsonError> {
+        let bytes = s.as_bytes();
+        let is_object = bytes[pos] == b'{';
+        let close = if is_object { b'}' } else { b']' };
+        let first = pending.len();
+        let mut pos = skip_ws(s, pos + 1);
+        if pos == bytes.len() || bytes[pos] != close {
+            loop {
+                let mut key = KStr::new();
+                if is_object {
+                    if pos == bytes.len() || bytes[pos] != b'"' {
+                        return Err(unexpected(s, pos));
+                    }
+                    let key_pos = pos;
+                    (key, pos) = match string::<S>(s, pos) {
+                        Ok((key, end)) => (key, skip_ws(s, end)),
+                        Err(err) => return Err(err),
+                    };
+                    if pos == bytes.len() || bytes[pos] != b':' {
+                        return Err(unexpected(s, pos));
+                    }
+                    let mut other = first;
+                    while other < pending.len() {
+                        if bytes_eq(pending.buf[other].0.as_bytes(), key.as_bytes()) {
+                            return Err(JsonError::new(JsonErrorKind::DuplicateKey, s, key_pos));
+                        }
+                        other += 1;
+                    }
+                    pos = skip_ws(s, pos + 1);
+                }
+                let value = match self.value(s, pos, pending) {
+                    Ok((value, end)) => {
+                        pos = skip_ws(s, end);
+                        value
+                    }
+                    Err(err) => return Err(err),
+                };
+                *pending = pending.push((key, value));
+                if pos < bytes.len() && bytes[pos] == b',' {
+                    pos = skip_ws(s, pos + 1);
+                } else if pos < bytes.len() && bytes[pos] == close {
+                    break;
+                } else {
+                    return Err(unexpected(s, pos));
+                }
+            }
+        }
+        let values_start = self.values.len();
+        let members_start = self.members.len();
+        let mut idx = first;
+        while idx < pending.len() {
+            let (key, value) = pending.buf[idx];
+            if is_object {
+                self.members.vec = self.members.vec.push((key, self.values.len()));
+            }
+            self.values = self.values.push(value);
+            idx += 1;
+        }
+        let len = pending.len() - first;
+        pending.cursor = first;
+        let container = if is_object {
+            JsonValue::Object {
+                start: members_start,
+                len,
+            }
+        } else {
+            JsonValue::Array {
+                start: values_start,
+                len,
+            }
+        };
+        Ok((container, pos + 1))
+    }
+}
+
+/// Children of the containers being parsed, with their key in objects
+type Pending<const N: usize, const S: usize> = KVec<(KStr<S>, JsonValue<S>), N>;
+
+const fn unexpected(s: &str, pos: usize) -> JsonError {
+    match char_at(s, pos) {
+        Some((c, _)) => JsonError::new(JsonErrorKind::UnexpectedChar(c), s, pos),
+        None => JsonError::new(JsonErrorKind::UnexpectedEnd, s, pos),
+    }
+}
+
+const fn skip_ws(s: &str, mut pos: usize) -> usize {
+    let bytes = s.as_bytes();
+    while pos < bytes.len() && matches!(bytes[pos], b' ' | b'\t' | b'\n' | b'\r') {
+        pos += 1;
+    }
+    pos
+}
+
+/// Parses the string starting at the `"` at `pos`, returns it and the index after it.
+const fn string<const S: usize>(s: &str, pos: usize) -> Result<(KStr<S>, usize), JsonError> {
+    let bytes = s.as_bytes();
+    let mut end = pos + 1;
+    while end < bytes.len() && bytes[end] != b'"' {
+        end += if bytes[end] == b'\\' { 2 } else { 1 };
+    }
+    if end >= bytes.len() {
+        return Err(unexpected(s, bytes.len()));
+    }
+    match unescape_json(s.split_at(end).0.split_at(pos + 1).1) {
+        Ok(string) => Ok((string, end + 1)),
+        Err(err) => Err(JsonError::new(
+            JsonErrorKind::InvalidString(err.kind),
+            s,
+            pos + 1 + err.offset,
+        )),
+    }
+}
+
+/// Checks the number starting at `pos`, returns the index after it.
+const fn number_end(s: &str, pos: usize) -> Result<usize, JsonError> {
+    let bytes = s.as_bytes();
+    let mut idx = pos;
+    if bytes[idx] == b'-' {
+        idx += 1;
+    }
+    if idx < bytes.len() && bytes[idx] == b'0' {
+        idx += 1;
+    } else {
+        idx = match digits_end(bytes, idx) {
+            Some(end) => end,
+            None => return Err(JsonError::new(JsonErrorKind::InvalidNumber, s, pos)),
+        };
+    }
+    if idx < bytes.len() && bytes[idx] == b'.' {
+        idx = match digits_end(bytes, idx + 1) {
+            Some(end) => end,
+            None => return Err(JsonError::new(JsonErrorKind::InvalidNumber, s, pos)),
+        };
+    }
+    if idx < bytes.len() && matches!(bytes[idx], b'e' | b'E') {
+        idx += 1;
+        if idx < bytes.len() && matches!(bytes[idx], b'+' | b'-') {
+            idx += 1;
+        }
+        idx = match digits_end(bytes, idx) {
+            Some(end) => end,
+            None => return Err(JsonError::new(JsonErrorKind::InvalidNumber, s, pos)),
+        };
+    }
+    Ok(idx)
+}
+
+/// Returns the index after the digits starting at `idx`, or None if there are none.
+const fn digits_end(bytes: &[u8], mut idx: usize) -> Option<usize> {
+    let start = idx;
+    while idx < bytes.len() && bytes[idx].is_ascii_digit() {
+        idx += 1;
+    }
+    if idx == start {
+        None
+    } else {
+        Some(idx)
+    }
+}
+
+const fn literal<const S: usize>(s: &str, pos: usize) -> Result<(JsonValue<S>, usize), JsonError> {
+    let bytes = s.as_bytes();
+    let (word, value): (&[u8], _) = match bytes[pos] {
+        b't' => (b"true", JsonValue::Bool(true)),
+        b'f' => (b"false", JsonValue::Bool(false)),
+        b'n' => (b"null", JsonValue::Null),
+        _ => return Err(unexpected(s, pos)),
+    };
+    let mut idx = 0;
+    while idx < word.len() {
+        if pos + idx == bytes.len() || bytes[pos + idx] != word[idx] {
+            return Err(unexpected(s, pos + idx));
+        }
+        idx += 1;
+    }
+    Ok((value, pos + word.len()))
+}
+
+#[cfg(test)]
+mod json_test {
+    use super::*;
+
+    const DOC: KJson<32, 16> = KJson::parse(
+        r#"{
+            "name": "konster",
+            "version": [0, 4, -1],
+            "nested": {"ok": true, "none": null, "pi": 2.5e0, "esc": "a\"\u00e9\ud83d\ude00"},
+            "empty": [{}, []]
+        }"#,
+    );
+    const _: () = {
+        assert!(matches!(DOC.get("nested.ok"), Some(JsonValue::Bool(true))));
+        assert!(matches!(DOC.get("version[2]"), Some(v) if matches!(v.as_i64(), Some(-1))));
+        assert!(DOC.get("version[3]").is_none());
+    };
+
+    #[test]
+    fn lookup() {
+        assert_eq!(DOC.get("name").and_then(|v| v.as_str()), Some("konster"));
+        assert_eq!(DOC.get("version[1]").and_then(|v| v.as_u64()), Some(4));
+        assert_eq!(DOC.get("version[2]").and_then(|v| v.as_u64()), None);
+        assert_eq!(DOC.get("nested.pi").and_then(|v| v.as_f64()), Some(2.5));
+        assert_eq!(DOC.get("nested.pi").and_then(|v| v.as_i64()), None);
+        assert_eq!(
+            DOC.get("nested.esc").and_then(|v| v.as_str()),
+            Some("a\"é😀")
+        );
+        assert!(DOC.get("nested.none").unwrap().is_null());
+        assert_eq!(
+            DOC.get("empty[0]"),
+            Some(&JsonValue::Object { start: 4, len: 0 })
+        );
+        assert_eq!(
+            DOC.get("empty[1]"),
+            Some(&JsonValue::Array { start: 8, len: 0 })
+        );
+        assert_eq!(DOC.get(""), Some(DOC.root()));
+        for path in [
+            "missing",
+            "name.x",
+            "version[",
+            "version[]",
+            "version.0",
+            "name[0]",
+            "nested..ok",
+            "empty[0]x",
+        ] {
+            assert_eq!(DOC.get(path), None, "{path}");
+        }
+        let root: KJson<4, 8> = KJson::parse(" [[1], 2] ");
+        assert_eq!(root.get("[0][0]").and_then(|v| v.as_u64()), Some(1));
+        assert_eq!(
+            KJson::<1, 32>::parse("-9223372036854775808")
+                .root()
+                .as_i64(),
+            Some(i64::MIN)
+        );
+        assert_eq!(
+            KJson::<1, 32>::parse("18446744073709551616")
+                .root()
+                .as_u64(),
+            None
+        );
+    }
+
+    #[test]
+    fn errors() {
+        for (doc, kind, line, column) in [
+            ("", JsonErrorKind::UnexpectedEnd, 1, 1),
+            ("[1, 2", JsonErrorKind::UnexpectedEnd, 1, 6),
+            (
+                "{\n  \"a\": tru }",
+                JsonErrorKind::UnexpectedChar(' '),
+                2,
+                11,
+            ),
+            ("[1,]", JsonErrorKind::UnexpectedChar(']'), 1, 4),
+            ("{\"a\" 1}", JsonErrorKind::UnexpectedChar('1'), 1, 6),
+            ("{1: 2}", JsonErrorKind::UnexpectedChar('1'), 1, 2),
+            ("[1] x", JsonErrorKind::UnexpectedChar('x'), 1, 5),
+            ("[\"é\", 01]", JsonErrorKind::UnexpectedChar('1'), 1, 8),
+            ("[1.]", JsonErrorKind::InvalidNumber, 1, 2),
+            ("-", JsonErrorKind::InvalidNumber, 1, 1),
+            ("[1e+]", JsonErrorKind::InvalidNumber, 1, 2),
+            (
+                "\"a\\q\"",
+                JsonErrorKind::InvalidString(EscapeErrorKind::UnknownEscape),
+                1,
+                3,
+            ),
+            (
+                "\"\\ud800\"",
+                JsonErrorKind::InvalidString(EscapeErrorKind::LoneSurrogate),
+                1,
+                2,
+            ),
+            (
+                "\"a\tb\"",
+                JsonErrorKind::InvalidString(EscapeErrorKind::UnescapedControl),
+                1,
+                3,
+            ),
+            ("\"abc", JsonErrorKind::UnexpectedEnd, 1, 5),
+            (
+                "{\"a\": 1,\n \"b\": {\"a\": 2, \"a\": 3}}",
+                JsonErrorKind::DuplicateKey,
+                2,
+                16,
+            ),
+        ] {
+            let err = KJson::<16, 8>::try_parse(doc).unwrap_err();
+            assert_eq!(
+                (err.kind, err.line, err.column),
+                (kind, line, column),
+                "{doc:?}"
+            );
+        }
+        assert_eq!(
+            KJson::<4, 4>::try_parse("[1,\n x]")
+                .unwrap_err()
+                .to_string(),
+            "unexpected char 'x' at line 2 column 2"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn overflow() {
+        let _ = KJson::<2, 4>::try_parse("[1, 2]");
+    }
+}
@@ -52,6 +52,7 @@ use crate::kvec::KVec;
 ///     }
 /// }
 /// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct KMap<K, V, const N: usize> {
     /// The backing Vector of the Map
     pub vec: KVec<(K, V), N>,
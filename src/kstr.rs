@@ -1,11 +1,33 @@
 use crate::kvec::KVec;
 
+mod case;
+mod escape;
+mod pad;
+mod parse;
+mod pattern;
+mod replace;
+mod shell;
+mod split;
+mod trim;
+pub(crate) use escape::unescape_json;
+pub use escape::{EscapeError, EscapeErrorKind};
+pub use pad::Alignment;
+pub(crate) use parse::{parse_i64, parse_u64};
+pub use parse::{ParseIntError, ParseIntErrorKind};
+pub(crate) use pattern::bytes_eq;
+pub use pattern::Pattern;
+pub use shell::{ShellSplitError, ShellSplitErrorKind};
+pub(crate) use split::split_once;
+pub use split::SplitCursor;
+pub(crate) use trim::{skip_spaces, trim_end, trim_start};
+
 /// String like struct usable on const context
 ///
-/// Currently only handles ASCII strings
+/// The inner buffer is always valid UTF-8, every method that mutates the String
+/// either works on whole chars or panics when it would split one.
 ///
 /// Its generic over its inner buffer size
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy)]
 pub struct KStr<const N: usize> {
     vec: KVec<u8, N>,
 }
@@ -22,49 +44,107 @@ impl<const N: usize> KStr<N> {
         }
     }
     /// Create a new String from [`std::primitive::str`]
+    /// # Panics: panic if the string doesn't fit in the buffer
     #[must_use]
     pub const fn from_str(string: &str) -> Self {
-        let string = string.as_bytes();
-        let mut buf = [0; N];
-        let mut idx = 0;
-        while idx < string.len() {
-            buf[idx] = string[idx];
-            idx += 1;
-        }
-        Self {
-            vec: KVec { buf, cursor: idx },
-        }
+        Self::new().extend(string.as_bytes())
     }
     /// Returns a new String with a given String appended onto its end.
+    /// # Panics: panic if the buffer is full
     #[must_use]
-    pub const fn push_str(mut self, other: &Self) -> Self {
-        let mut idx = 0;
-        while idx < other.len() {
-            self = self.push(*other.get_unchecked(idx));
-            idx += 1;
+    pub const fn push_str(self, other: &Self) -> Self {
+        self.extend(other.as_bytes())
+    }
+    /// Returns a new String with a given ASCII elem appended to it.
+    /// # Panics: panic if the buffer is full or if elem is not ASCII, use [`KStr::push_char`] instead
+    #[must_use]
+    pub const fn push(self, elem: u8) -> Self {
+        if !elem.is_ascii() {
+            panic!("Can not push a non ASCII byte, use push_char instead");
         }
-        self
+        self.extend(&[elem])
     }
-    /// Returns a new String with a given elem appended to it.
+    /// Returns a new String with a given char appended to it, encoded as UTF-8.
+    /// # Panics: panic if the buffer is full
     #[must_use]
-    pub const fn push(mut self, elem: u8) -> Self {
-        self.vec.buf[self.vec.cursor] = elem;
-        self.vec.cursor += 1;
-        self
+    pub const fn push_char(self, c: char) -> Self {
+        let mut bytes = [0; 4];
+        self.extend(c.encode_utf8(&mut bytes).as_bytes())
     }
     /// Returns an option of a tuple of:
     /// - new String without the last element
     /// - the last element
+    ///
     /// Returns None if its empty.
+    /// # Panics: panic if the last char is not ASCII, use [`KStr::pop_char`] instead
     #[must_use]
     pub const fn pop(mut self) -> Option<(Self, u8)> {
         if self.is_empty() {
             return None;
         }
+        let val = self.vec.buf[self.vec.cursor - 1];
+        if !val.is_ascii() {
+            panic!("Can not pop a non ASCII char as a byte, use pop_char instead");
+        }
         self.vec.cursor -= 1;
-        let val = self.vec.buf[self.vec.cursor];
         Some((self, val))
     }
+    /// Returns an option of a tuple of:
+    /// - new String without the last char
+    /// - the last char
+    ///
+    /// Returns None if its empty.
+    #[must_use]
+    pub const fn pop_char(mut self) -> Option<(Self, char)> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut start = self.len() - 1;
+        while !self.is_char_boundary(start) {
+            start -= 1;
+        }
+        let (c, _) = decode_char(self.as_bytes(), start);
+        self.vec.cursor = start;
+        Some((self, c))
+    }
+    /// Returns the number of chars in the String, which can be less then its length in bytes.
+    pub const fn chars_count(&self) -> usize {
+        let mut idx = 0;
+        let mut count = 0;
+        while idx < self.len() {
+            if self.is_char_boundary(idx) {
+                count += 1;
+            }
+            idx += 1;
+        }
+        count
+    }
+    /// Returns true if the byte at the index is the start of a char, or if the index is equal to the length.
+    pub const fn is_char_boundary(&self, idx: usize) -> bool {
+        if idx == self.len() {
+            return true;
+        }
+        match self.get(idx) {
+            // UTF-8 continuation bytes look like 0b10xx_xxxx
+            Some(b) => (*b as i8) >= -0x40,
+            None => false,
+        }
+    }
+    /// Returns an option of a tuple of:
+    /// - the char starting at the byte index
+    /// - the byte index of the next char
+    ///
+    /// Returns None if the index is out of bounds or not on a char boundary.
+    pub const fn char_at(&self, idx: usize) -> Option<(char, usize)> {
+        if idx >= self.len() || !self.is_char_boundary(idx) {
+            return None;
+        }
+        Some(decode_char(self.as_bytes(), idx))
+    }
+    /// Returns a cursor over the chars of the String and their byte indices.
+    pub const fn char_indices(&self) -> CharIndices<'_, N> {
+        CharIndices { str: self, idx: 0 }
+    }
     /// Parses the String into a usize.
     /// # Panics: panic if the string is empty
     pub const fn parse_usize(self) -> usize {
@@ -86,67 +166,6 @@ impl<const N: usize> KStr<N> {
         }
         result as _
     }
-    /// Returns a Vector of the Strings by splitting the string on the specified char
-    /// The backing buffer length of the Vector, and the backing buffer length for each string needs to specified as const generics.
-    pub const fn split<const L: usize, const NN: usize>(&self, split: u8) -> KVec<KStr<NN>, L> {
-        let mut idx = 0;
-        let mut vec = KVec {
-            buf: [KStr::<NN>::new(); L],
-            cursor: 0,
-        };
-        let mut line = KStr::new();
-        while idx < self.len() {
-            let elem = self.get_unchecked(idx);
-            if *elem == split {
-                vec = push_to_lines_vec(vec, line);
-                line = line.clear();
-            } else {
-                line = line.push(*elem);
-            }
-
-            idx += 1;
-        }
-        if !line.is_empty() {
-            vec = push_to_lines_vec(vec, line);
-        }
-        vec
-    }
-    /// Returns a Vector of the Strings, by splitting the original String by white space.
-    /// The backing buffer length of the lines, and the backing buffer length for each string needs to specified as const generics.
-    pub const fn split_whitespace<const L: usize, const NN: usize>(&self) -> KVec<KStr<NN>, L> {
-        let mut idx = 0;
-        let mut vec = KVec {
-            buf: [KStr::<NN>::new(); L],
-            cursor: 0,
-        };
-        let mut line = KStr::new();
-        while idx < self.len() {
-            let elem = self.get_unchecked(idx);
-            if *elem == b' ' {
-                vec = push_to_lines_vec(vec, line);
-                line = line.clear();
-                while let Some(c) = self.get(idx + 1) {
-                    if *c != b' ' {
-                        break;
-                    }
-                    idx += 1;
-                }
-            } else {
-                line = line.push(*elem);
-            }
-
-            idx += 1;
-        }
-        if !line.is_empty() {
-            vec = push_to_lines_vec(vec, line);
-        }
-        vec
-    }
-    /// Returns a Vector of the String lines
-    /// The backing buffer length of the lines, and the backing buffer length for each string needs to specified as const generics.
-    pub const fn lines<const L: usize, const NN: usize>(&self) -> KVec<KStr<NN>, L> {
-        self.split(b'\n')
-    }
     // Forword kvec methods
     /// Returns a new String with elements cleared.
     #[must_use]
@@ -174,27 +193,163 @@ impl<const N: usize> KStr<N> {
     pub const fn last(&self) -> Option<&u8> {
         self.vec.last()
     }
+    /// Returns the String bytes.
+    pub const fn as_bytes(&self) -> &[u8] {
+        self.vec.buf.split_at(self.vec.cursor).0
+    }
+    /// Create an [`std::primitive::str`] from this String
+    pub const fn as_str(&self) -> &str {
+        match std::str::from_utf8(self.as_bytes()) {
+            Ok(s) => s,
+            Err(_) => unreachable!(),
+        }
+    }
+    /// Appends the decimal representation of the number.
+    pub(crate) const fn push_usize(self, n: usize) -> Self {
+        self.push_u64(n as u64)
+    }
+    /// Appends the decimal representation of the number.
+    pub(crate) const fn push_u64(self, n: u64) -> Self {
+        let mut digits = [0; 20];
+        let mut idx = digits.len();
+        let mut n = n;
+        loop {
+            idx -= 1;
+            digits[idx] = b'0' + (n % 10) as u8;
+            n /= 10;
+            if n == 0 {
+                break;
+            }
+        }
+        self.extend(digits.split_at(idx).1)
+    }
+    /// Appends bytes that are known to keep the buffer valid UTF-8.
+    pub(crate) const fn extend(mut self, bytes: &[u8]) -> Self {
+        if self.vec.cursor + bytes.len() > N {
+            panic!("KStr capacity exceeded");
+        }
+        let mut idx = 0;
+        while idx < bytes.len() {
+            self.vec.buf[self.vec.cursor] = bytes[idx];
+            self.vec.cursor += 1;
+            idx += 1;
+        }
+        self
+    }
 }
 
-// Runtime methods
-impl<const N: usize> KStr<N> {
-    /// [Runtime method] Create an [`std::primitive::str`] from this String
-    /// # Panics: Panic if the string in not valid UTF-8
-    pub fn as_str(&self) -> &str {
-        std::str::from_utf8(&self.vec.buf[..self.vec.cursor]).unwrap()
+/// Cursor over the chars of a [`KStr`] and their byte indices, created by [`KStr::char_indices`]
+///
+/// Use [`CharIndices::next`] in const context, or it as an [`Iterator`] at runtime.
+#[derive(Clone, Copy, Debug)]
+pub struct CharIndices<'a, const N: usize> {
+    str: &'a KStr<N>,
+    idx: usize,
+}
+impl<'a, const N: usize> CharIndices<'a, N> {
+    /// Returns an option of a tuple of:
+    /// - the advanced cursor
+    /// - the byte index of the char and the char
+    ///
+    /// Returns None when all chars are consumed.
+    #[allow(clippy::should_implement_trait)]
+    pub const fn next(self) -> Option<(Self, (usize, char))> {
+        match self.str.char_at(self.idx) {
+            Some((c, next)) => Some((
+                Self {
+                    str: self.str,
+                    idx: next,
+                },
+                (self.idx, c),
+            )),
+            None => None,
+        }
+    }
+}
+impl<const N: usize> Iterator for CharIndices<'_, N> {
+    type Item = (usize, char);
+    fn next(&mut self) -> Option<Self::Item> {
+        let (next, item) = CharIndices::next(*self)?;
+        *self = next;
+        Some(item)
+    }
+}
+
+impl<const N: usize> PartialEq for KStr<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+impl<const N: usize> Eq for KStr<N> {}
+impl<const N: usize> Default for KStr<N> {
+    fn default() -> Self {
+        Self::new()
     }
 }
 impl<const N: usize> std::fmt::Debug for KStr<N> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.as_str())
+        std::fmt::Debug::fmt(self.as_str(), f)
     }
 }
 
 // Helpers
+/// Decodes the char of a [`std::primitive::str`] starting at the byte index, returning it with the byte index of the next char.
+pub(crate) const fn char_at(s: &str, idx: usize) -> Option<(char, usize)> {
+    if idx >= s.len() || !s.is_char_boundary(idx) {
+        return None;
+    }
+    Some(decode_char(s.as_bytes(), idx))
+}
+/// Decodes the char of valid UTF-8 bytes starting at the byte index, which must be a char boundary.
+const fn decode_char(bytes: &[u8], idx: usize) -> (char, usize) {
+    let first = bytes[idx] as u32;
+    let (width, mut code) = if first < 0x80 {
+        (1, first)
+    } else if first < 0xE0 {
+        (2, first & 0x1F)
+    } else if first < 0xF0 {
+        (3, first & 0x0F)
+    } else {
+        (4, first & 0x07)
+    };
+    let mut offset = 1;
+    while offset < width {
+        code = (code << 6) | (bytes[idx + offset] as u32 & 0x3F);
+        offset += 1;
+    }
+    match char::from_u32(code) {
+        Some(c) => (c, idx + width),
+        None => unreachable!(),
+    }
+}
+pub(crate) const HEX_LOWER: &[u8; 16] = b"0123456789abcdef";
+pub(crate) const HEX_UPPER: &[u8; 16] = b"0123456789ABCDEF";
+
+/// Returns the value of an ASCII hex digit.
+pub(crate) const fn hex_digit(b: u8) -> Option<u8> {
+    match b {
+        b'0'..=b'9' => Some(b - b'0'),
+        b'a'..=b'f' => Some(b - b'a' + 10),
+        b'A'..=b'F' => Some(b - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Returns the byte index of the char that ends at the byte index.
+pub(crate) const fn prev_char_boundary(s: &str, mut idx: usize) -> usize {
+    idx -= 1;
+    while !s.is_char_boundary(idx) {
+        idx -= 1;
+    }
+    idx
+}
 const fn push_to_lines_vec<const N: usize, const L: usize>(
     mut vec: KVec<KStr<N>, L>,
     elem: KStr<N>,
 ) -> KVec<KStr<N>, L> {
+    if vec.cursor == L {
+        panic!("Split produced more Strings then the Vector capacity");
+    }
     vec.buf[vec.cursor] = elem;
     vec.cursor += 1;
     vec
@@ -204,6 +359,26 @@ const fn push_to_lines_vec<const N: usize, const L: usize>(
 mod kost_test {
     use super::*;
 
+    // Char level methods must stay linear on long Strings to fit in the const eval limit
+    const LONG: KStr<8192> = match std::str::from_utf8(&[b'a'; 8000]) {
+        Ok(s) => KStr::from_str(s),
+        Err(_) => unreachable!(),
+    };
+    const _: () = {
+        let mut chars = LONG.char_indices();
+        let mut count = 0;
+        while let Some((next, _)) = chars.next() {
+            chars = next;
+            count += 1;
+        }
+        assert!(count == 8000);
+        let mut str = LONG;
+        while let Some((next, _)) = str.pop_char() {
+            str = next;
+        }
+        assert!(str.is_empty());
+    };
+
     const _: () = {
         let mut str = KStr::<20>::new();
         str = str.push(4);
@@ -220,4 +395,36 @@ mod kost_test {
             None => unreachable!(),
         }
     };
+
+    const _: () = {
+        let mut str = KStr::<20>::from_str("aé");
+        str = str.push_char('€').push_char('🦀');
+        if str.len() != 10 || str.chars_count() != 4 {
+            panic!("wrong length");
+        }
+        if !matches!(str.char_at(1), Some(('é', 3))) || str.char_at(2).is_some() {
+            panic!("wrong char_at");
+        }
+        let (str, c) = match str.pop_char() {
+            Some(res) => res,
+            None => unreachable!(),
+        };
+        if c != '🦀' || str.len() != 6 {
+            panic!("wrong pop_char");
+        }
+    };
+
+    #[test]
+    fn char_indices() {
+        let str = KStr::<20>::from_str("aé€");
+        let v: Vec<_> = str.char_indices().collect();
+        assert_eq!(v, "aé€".char_indices().collect::<Vec<_>>());
+        assert_eq!(str.as_str(), "aé€");
+    }
+
+    #[test]
+    #[should_panic]
+    fn pop_non_ascii() {
+        let _ = KStr::<4>::from_str("é").pop();
+    }
 }
@@ -0,0 +1,202 @@
+use super::{char_at, KStr};
+
+// Only ASCII letters change case, `char::to_lowercase` and `char::to_uppercase` aren't usable in
+// const context.
+impl<const N: usize> KStr<N> {
+    /// Returns a new String with the ASCII letters mapped to uppercase.
+    #[must_use]
+    pub const fn to_ascii_uppercase(mut self) -> Self {
+        let mut idx = 0;
+        while idx < self.len() {
+            self.vec.buf[idx] = self.vec.buf[idx].to_ascii_uppercase();
+            idx += 1;
+        }
+        self
+    }
+    /// Returns a new String with the ASCII letters mapped to lowercase.
+    #[must_use]
+    pub const fn to_ascii_lowercase(mut self) -> Self {
+        let mut idx = 0;
+        while idx < self.len() {
+            self.vec.buf[idx] = self.vec.buf[idx].to_ascii_lowercase();
+            idx += 1;
+        }
+        self
+    }
+    /// Returns true if the String is equal to other, ignoring ASCII case.
+    pub const fn eq_ignore_ascii_case(&self, other: &str) -> bool {
+        self.as_bytes().eq_ignore_ascii_case(other.as_bytes())
+    }
+    /// Returns a new String of capacity `M` in `snake_case`.
+    ///
+    /// The String is split in words on non alphanumeric chars, on a lowercase letter followed by an
+    /// uppercase one and before the last letter of an acronym (`HTTPServer` is `http_server`).
+    /// Digits stay in the word they follow.
+    /// # Panics: panic if the result doesn't fit in `M`
+    #[must_use]
+    pub const fn to_snake_case<const M: usize>(&self) -> KStr<M> {
+        convert_case(self.as_str(), "_", Case::Lower, Case::Lower)
+    }
+    /// Returns a new String of capacity `M` in `kebab-case`, see [`KStr::to_snake_case`] for the word rules.
+    /// # Panics: panic if the result doesn't fit in `M`
+    #[must_use]
+    pub const fn to_kebab_case<const M: usize>(&self) -> KStr<M> {
+        convert_case(self.as_str(), "-", Case::Lower, Case::Lower)
+    }
+    /// Returns a new String of capacity `M` in `SHOUTY_SNAKE_CASE`, see [`KStr::to_snake_case`] for the word rules.
+    /// # Panics: panic if the result doesn't fit in `M`
+    #[must_use]
+    pub const fn to_shouty_snake_case<const M: usize>(&self) -> KStr<M> {
+        convert_case(self.as_str(), "_", Case::Upper, Case::Upper)
+    }
+    /// Returns a new String of capacity `M` in `camelCase`, see [`KStr::to_snake_case`] for the word rules.
+    /// # Panics: panic if the result doesn't fit in `M`
+    #[must_use]
+    pub const fn to_camel_case<const M: usize>(&self) -> KStr<M> {
+        convert_case(self.as_str(), "", Case::Lower, Case::Title)
+    }
+    /// Returns a new String of capacity `M` in `PascalCase`, see [`KStr::to_snake_case`] for the word rules.
+    /// # Panics: panic if the result doesn't fit in `M`
+    #[must_use]
+    pub const fn to_pascal_case<const M: usize>(&self) -> KStr<M> {
+        convert_case(self.as_str(), "", Case::Title, Case::Title)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Case {
+    Lower,
+    Upper,
+    Title,
+}
+
+const fn convert_case<const M: usize>(s: &str, sep: &str, first: Case, rest: Case) -> KStr<M> {
+    let mut out = KStr::new();
+    let mut from = 0;
+    let mut first_word = true;
+    while let Some((start, end)) = next_word(s, from) {
+        if !first_word {
+            out = out.extend(sep.as_bytes());
+        }
+        let case = if first_word { first } else { rest };
+        let mut idx = start;
+        while idx < end {
+            let c = s.as_bytes()[idx];
+            out = out.extend(&[match case {
+                Case::Title if idx == start => c.to_ascii_uppercase(),
+                Case::Upper => c.to_ascii_uppercase(),
+                _ => c.to_ascii_lowercase(),
+            }]);
+            idx += 1;
+        }
+        first_word = false;
+        from = end;
+    }
+    out
+}
+
+/// Chars that are part of words, non ASCII chars are kept as is.
+const fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || !c.is_ascii()
+}
+
+/// Returns the byte range of the next word in `s[from..]`.
+const fn next_word(s: &str, mut from: usize) -> Option<(usize, usize)> {
+    while let Some((c, next)) = char_at(s, from) {
+        if is_word_char(c) {
+            break;
+        }
+        from = next;
+    }
+    if from == s.len() {
+        return None;
+    }
+
+    // Tracks the case of the last letter, digits don't change it.
+    let mut last_lowercase = false;
+    let mut idx = from;
+    while let Some((c, next)) = char_at(s, idx) {
+        if !is_word_char(c) {
+            return Some((from, idx));
+        }
+        if c.is_lowercase() {
+            last_lowercase = true;
+        } else if c.is_uppercase() {
+            last_lowercase = false;
+        }
+        match char_at(s, next) {
+            // fooBar
+            Some((n, _)) if last_lowercase && n.is_uppercase() => return Some((from, next)),
+            // HTTPServer
+            Some((n, after)) if c.is_uppercase() && n.is_uppercase() => {
+                if let Some((nn, _)) = char_at(s, after) {
+                    if nn.is_lowercase() {
+                        return Some((from, next));
+                    }
+                }
+            }
+            _ => (),
+        }
+        idx = next;
+    }
+    Some((from, idx))
+}
+
+#[cfg(test)]
+mod case_test {
+    use super::*;
+
+    const NAME: KStr<20> = KStr::from_str("HTTPServer2Config");
+    const SNAKE: KStr<24> = NAME.to_snake_case();
+    const _: () = {
+        assert!(SNAKE.eq_ignore_ascii_case("HTTP_SERVER2_CONFIG"));
+        assert!(NAME
+            .to_ascii_lowercase()
+            .eq_ignore_ascii_case("httpserver2config"));
+    };
+
+    #[test]
+    fn identifiers() {
+        let cases = [
+            ("HTTPServer", "http_server", "httpServer", "HttpServer"),
+            (
+                "fooBar_baz-qux",
+                "foo_bar_baz_qux",
+                "fooBarBazQux",
+                "FooBarBazQux",
+            ),
+            (
+                "XMLHttpRequest",
+                "xml_http_request",
+                "xmlHttpRequest",
+                "XmlHttpRequest",
+            ),
+            (
+                "version2Beta",
+                "version2_beta",
+                "version2Beta",
+                "Version2Beta",
+            ),
+            ("ABC123def", "abc123def", "abc123def", "Abc123def"),
+            (
+                "  already_snake  ",
+                "already_snake",
+                "alreadySnake",
+                "AlreadySnake",
+            ),
+            ("unix 2 time", "unix_2_time", "unix2Time", "Unix2Time"),
+            ("", "", "", ""),
+        ];
+        for (input, snake, camel, pascal) in cases {
+            let s = KStr::<32>::from_str(input);
+            assert_eq!(s.to_snake_case::<32>().as_str(), snake);
+            assert_eq!(s.to_kebab_case::<32>().as_str(), snake.replace('_', "-"));
+            assert_eq!(
+                s.to_shouty_snake_case::<32>().as_str(),
+                snake.to_ascii_uppercase()
+            );
+            assert_eq!(s.to_camel_case::<32>().as_str(), camel);
+            assert_eq!(s.to_pascal_case::<32>().as_str(), pascal);
+        }
+    }
+}
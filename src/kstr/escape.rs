@@ -0,0 +1,443 @@
+use super::{char_at, hex_digit, KStr, HEX_LOWER};
+use crate::error::impl_error;
+
+/// Error returned when unescaping a malformed String
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EscapeError {
+    /// What is wrong with the escape
+    pub kind: EscapeErrorKind,
+    /// Byte index of the start of the malformed escape
+    pub offset: usize,
+}
+
+/// The different ways an escape can be malformed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EscapeErrorKind {
+    /// A backslash followed by an unknown char
+    UnknownEscape,
+    /// The String ends in the middle of an escape
+    UnterminatedEscape,
+    /// An escape is missing hex digits or contains an invalid one
+    InvalidHex,
+    /// A `\x` escape above `\x7F`
+    HexOutOfRange,
+    /// A `\u` escape that is not a valid char, like a surrogate or a value above `10FFFF`
+    InvalidCodePoint,
+    /// A JSON `\u` surrogate that is not part of a pair
+    LoneSurrogate,
+    /// A JSON control char that is not escaped
+    UnescapedControl,
+}
+
+impl EscapeErrorKind {
+    /// Returns a description of the error.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            EscapeErrorKind::UnknownEscape => "unknown escape",
+            EscapeErrorKind::UnterminatedEscape => "unterminated escape",
+            EscapeErrorKind::InvalidHex => "invalid hex digits in escape",
+            EscapeErrorKind::HexOutOfRange => "hex escape above \\x7F",
+            EscapeErrorKind::InvalidCodePoint => "escape is not a valid char",
+            EscapeErrorKind::LoneSurrogate => "unpaired surrogate escape",
+            EscapeErrorKind::UnescapedControl => "unescaped control char",
+        }
+    }
+}
+
+impl EscapeError {
+    const fn new(kind: EscapeErrorKind, offset: usize) -> Self {
+        Self { kind, offset }
+    }
+}
+impl_error!(EscapeError, " at byte " offset);
+
+impl<const N: usize> KStr<N> {
+    /// Returns a new String of capacity `M` with the chars escaped like [`str::escape_default`].
+    /// # Panics: panic if the result doesn't fit in `M`
+    #[must_use]
+    pub const fn escape_default<const M: usize>(&self) -> KStr<M> {
+        let mut out = KStr::new();
+        let s = self.as_str();
+        let mut idx = 0;
+        while let Some((c, next)) = char_at(s, idx) {
+            out = match c {
+                '\t' | '\r' | '\n' | '\\' | '\'' | '"' => escape_simple(out, c),
+                '\x20'..='\x7e' => out.push_char(c),
+                _ => escape_unicode(out, c),
+            };
+            idx = next;
+        }
+        out
+    }
+    /// Returns a new String of capacity `M` with the chars escaped like [`str::escape_debug`].
+    ///
+    /// Unlike std, only control chars are considered not printable,
+    /// the Unicode printable tables aren't available in const context.
+    /// # Panics: panic if the result doesn't fit in `M`
+    #[must_use]
+    pub const fn escape_debug<const M: usize>(&self) -> KStr<M> {
+        let mut out = KStr::new();
+        let s = self.as_str();
+        let mut idx = 0;
+        while let Some((c, next)) = char_at(s, idx) {
+            out = match c {
+                '\0' => out.extend(b"\\0"),
+                '\t' | '\r' | '\n' | '\\' | '\'' | '"' => escape_simple(out, c),
+                '\x00'..='\x1f' | '\x7f'..='\u{9f}' => escape_unicode(out, c),
+                _ => out.push_char(c),
+            };
+            idx = next;
+        }
+        out
+    }
+    /// Returns a new String of capacity `M` escaped to be the content of a JSON string.
+    ///
+    /// Quotes, backslashes and control chars are escaped, the other chars are kept as is.
+    /// # Panics: panic if the result doesn't fit in `M`
+    #[must_use]
+    pub const fn escape_json<const M: usize>(&self) -> KStr<M> {
+        let mut out = KStr::new();
+        let bytes = self.as_bytes();
+        let mut idx = 0;
+        while idx < bytes.len() {
+            let b = bytes[idx];
+            out = match b {
+                b'"' => out.extend(b"\\\""),
+                b'\\' => out.extend(b"\\\\"),
+                b'\x08' => out.extend(b"\\b"),
+                b'\x0c' => out.extend(b"\\f"),
+                b'\n' => out.extend(b"\\n"),
+                b'\r' => out.extend(b"\\r"),
+                b'\t' => out.extend(b"\\t"),
+                0..=0x1f => out
+                    .extend(b"\\u00")
+                    .extend(&[HEX_LOWER[(b >> 4) as usize], HEX_LOWER[(b & 0xf) as usize]]),
+                // Whole chars are copied byte by byte
+                _ => out.extend(&[b]),
+            };
+            idx += 1;
+        }
+        out
+    }
+    /// Returns a new String of capacity `M` with the escapes of a Rust string literal replaced by the chars they represent.
+    ///
+    /// Supports `\n \r \t \\ \0 \' \"`, `\x7F` ASCII escapes, `\u{10FFFF}` Unicode escapes and
+    /// a backslash before a newline that skips the following white space.
+    /// # Panics: panic if the result doesn't fit in `M`
+    pub const fn unescape_rust<const M: usize>(&self) -> Result<KStr<M>, EscapeError> {
+        let mut out = KStr::new();
+        let s = self.as_str();
+        let bytes = s.as_bytes();
+        let mut idx = 0;
+        while idx < bytes.len() {
+            if bytes[idx] != b'\\' {
+                let (c, next) = match char_at(s, idx) {
+                    Some(res) => res,
+                    None => unreachable!(),
+                };
+                out = out.push_char(c);
+                idx = next;
+                continue;
+            }
+            let start = idx;
+            if idx + 1 == bytes.len() {
+                return Err(EscapeError::new(EscapeErrorKind::UnterminatedEscape, start));
+            }
+            idx += 2;
+            out = match bytes[start + 1] {
+                b'n' => out.push(b'\n'),
+                b'r' => out.push(b'\r'),
+                b't' => out.push(b'\t'),
+                b'\\' => out.push(b'\\'),
+                b'0' => out.push(b'\0'),
+                b'\'' => out.push(b'\''),
+                b'"' => out.push(b'"'),
+                b'x' => {
+                    let value = match parse_hex(bytes, idx, 2) {
+                        Some(value) => value,
+                        None => return Err(hex_error(bytes, idx, 2, start)),
+                    };
+                    if value > 0x7f {
+                        return Err(EscapeError::new(EscapeErrorKind::HexOutOfRange, start));
+                    }
+                    idx += 2;
+                    out.push(value as u8)
+                }
+                b'u' => {
+                    if idx == bytes.len() {
+                        return Err(EscapeError::new(EscapeErrorKind::UnterminatedEscape, start));
+                    }
+                    if bytes[idx] != b'{' {
+                        return Err(EscapeError::new(EscapeErrorKind::InvalidHex, start));
+                    }
+                    idx += 1;
+                    let mut value: u32 = 0;
+                    let mut digits = 0;
+                    loop {
+                        if idx == bytes.len() {
+                            return Err(EscapeError::new(
+                                EscapeErrorKind::UnterminatedEscape,
+                                start,
+                            ));
+                        }
+                        let b = bytes[idx];
+                        idx += 1;
+                        if b == b'}' {
+                            break;
+                        }
+                        if b == b'_' && digits > 0 {
+                            continue;
+                        }
+                        match hex_digit(b) {
+                            Some(v) if digits < 6 => value = value << 4 | v as u32,
+                            _ => return Err(EscapeError::new(EscapeErrorKind::InvalidHex, start)),
+                        }
+                        digits += 1;
+                    }
+                    if digits == 0 {
+                        return Err(EscapeError::new(EscapeErrorKind::InvalidHex, start));
+                    }
+                    match char::from_u32(value) {
+                        Some(c) => out.push_char(c),
+                        None => {
+                            return Err(EscapeError::new(EscapeErrorKind::InvalidCodePoint, start))
+                        }
+                    }
+                }
+                b'\n' => {
+                    while idx < bytes.len() && bytes[idx].is_ascii_whitespace() {
+                        idx += 1;
+                    }
+                    out
+                }
+                _ => return Err(EscapeError::new(EscapeErrorKind::UnknownEscape, start)),
+            };
+        }
+        Ok(out)
+    }
+    /// Returns a new String of capacity `M` with the escapes of a JSON string content replaced by the chars they represent.
+    ///
+    /// Supports `\" \\ \/ \b \f \n \r \t` and `\uFFFF` escapes, including surrogate pairs.
+    /// # Panics: panic if the result doesn't fit in `M`
+    pub const fn unescape_json<const M: usize>(&self) -> Result<KStr<M>, EscapeError> {
+        unescape_json(self.as_str())
+    }
+}
+
+pub(crate) const fn unescape_json<const M: usize>(s: &str) -> Result<KStr<M>, EscapeError> {
+    let mut out = KStr::new();
+    let bytes = s.as_bytes();
+    let mut idx = 0;
+    while idx < bytes.len() {
+        if bytes[idx] < 0x20 {
+            return Err(EscapeError::new(EscapeErrorKind::UnescapedControl, idx));
+        }
+        if bytes[idx] != b'\\' {
+            let (c, next) = match char_at(s, idx) {
+                Some(res) => res,
+                None => unreachable!(),
+            };
+            out = out.push_char(c);
+            idx = next;
+            continue;
+        }
+        let start = idx;
+        if idx + 1 == bytes.len() {
+            return Err(EscapeError::new(EscapeErrorKind::UnterminatedEscape, start));
+        }
+        idx += 2;
+        out = match bytes[start + 1] {
+            b'"' => out.push(b'"'),
+            b'\\' => out.push(b'\\'),
+            b'/' => out.push(b'/'),
+            b'b' => out.push(b'\x08'),
+            b'f' => out.push(b'\x0c'),
+            b'n' => out.push(b'\n'),
+            b'r' => out.push(b'\r'),
+            b't' => out.push(b'\t'),
+            b'u' => {
+                let mut value = match parse_hex(bytes, idx, 4) {
+                    Some(value) => value,
+                    None => return Err(hex_error(bytes, idx, 4, start)),
+                };
+                idx += 4;
+                if value >= 0xDC00 && value <= 0xDFFF {
+                    return Err(EscapeError::new(EscapeErrorKind::LoneSurrogate, start));
+                }
+                if value >= 0xD800 && value <= 0xDBFF {
+                    if idx + 1 >= bytes.len() || bytes[idx] != b'\\' || bytes[idx + 1] != b'u' {
+                        return Err(EscapeError::new(EscapeErrorKind::LoneSurrogate, start));
+                    }
+                    let low = match parse_hex(bytes, idx + 2, 4) {
+                        Some(low) => low,
+                        None => return Err(hex_error(bytes, idx + 2, 4, idx)),
+                    };
+                    if low < 0xDC00 || low > 0xDFFF {
+                        return Err(EscapeError::new(EscapeErrorKind::LoneSurrogate, start));
+                    }
+                    idx += 6;
+                    value = 0x10000 + ((value - 0xD800) << 10) + (low - 0xDC00);
+                }
+                match char::from_u32(value) {
+                    Some(c) => out.push_char(c),
+                    None => unreachable!(),
+                }
+            }
+            _ => return Err(EscapeError::new(EscapeErrorKind::UnknownEscape, start)),
+        };
+    }
+    Ok(out)
+}
+
+/// Parses exactly `len` hex digits starting at `idx`.
+const fn parse_hex(bytes: &[u8], idx: usize, len: usize) -> Option<u32> {
+    if idx + len > bytes.len() {
+        return None;
+    }
+    let mut value = 0;
+    let mut offset = 0;
+    while offset < len {
+        match hex_digit(bytes[idx + offset]) {
+            Some(v) => value = value << 4 | v as u32,
+            None => return None,
+        }
+        offset += 1;
+    }
+    Some(value)
+}
+
+/// Tells apart an escape cut by the end of the String from one with invalid digits.
+const fn hex_error(bytes: &[u8], idx: usize, len: usize, start: usize) -> EscapeError {
+    let mut offset = 0;
+    while idx + offset < bytes.len() && offset < len {
+        if hex_digit(bytes[idx + offset]).is_none() {
+            return EscapeError::new(EscapeErrorKind::InvalidHex, start);
+        }
+        offset += 1;
+    }
+    EscapeError::new(EscapeErrorKind::UnterminatedEscape, start)
+}
+
+const fn escape_simple<const M: usize>(out: KStr<M>, c: char) -> KStr<M> {
+    out.extend(match c {
+        '\t' => b"\\t",
+        '\r' => b"\\r",
+        '\n' => b"\\n",
+        '\\' => b"\\\\",
+        '\'' => b"\\'",
+        '"' => b"\\\"",
+        _ => unreachable!(),
+    })
+}
+
+/// Appends `\u{XXXX}` with the lowercase hex value of the char without leading zeroes.
+const fn escape_unicode<const M: usize>(mut out: KStr<M>, c: char) -> KStr<M> {
+    let value = c as u32;
+    out = out.extend(b"\\u{");
+    let mut shift = 20;
+    while shift > 0 && (value >> shift) == 0 {
+        shift -= 4;
+    }
+    loop {
+        out = out.extend(&[HEX_LOWER[((value >> shift) & 0xf) as usize]]);
+        if shift == 0 {
+            break;
+        }
+        shift -= 4;
+    }
+    out.extend(b"}")
+}
+
+#[cfg(test)]
+mod escape_test {
+    use super::*;
+
+    const ESCAPED: KStr<32> = KStr::from_str(r#"a\"b\u00e9\ud83e\udd80\n"#);
+    const UNESCAPED: KStr<16> = match ESCAPED.unescape_json() {
+        Ok(s) => s,
+        Err(e) => e.panic(),
+    };
+    const _: () = assert!(UNESCAPED.chars_count() == 6);
+    const _: () = {
+        let long = match std::str::from_utf8(&[b'a'; 8000]) {
+            Ok(s) => KStr::<8192>::from_str(s),
+            Err(_) => unreachable!(),
+        };
+        match long.unescape_rust::<8192>() {
+            Ok(s) => assert!(s.len() == 8000),
+            Err(e) => e.panic(),
+        }
+    };
+
+    #[test]
+    fn parity_with_std() {
+        for s in [
+            "",
+            "abc",
+            "tab\there",
+            "quote'\"\\",
+            "é€🦀",
+            "\0\x01\x7f\u{85}",
+            "line\r\n",
+        ] {
+            let k = KStr::<32>::from_str(s);
+            assert_eq!(
+                k.escape_default::<128>().as_str(),
+                s.escape_default().to_string()
+            );
+            assert_eq!(
+                k.escape_debug::<128>().as_str(),
+                s.escape_debug().to_string()
+            );
+            assert_eq!(format!("{k:?}"), format!("{s:?}"));
+
+            let rust = k.escape_default::<128>();
+            assert_eq!(rust.unescape_rust::<32>(), Ok(k));
+            let json = k.escape_json::<128>();
+            assert_eq!(json.unescape_json::<32>(), Ok(k));
+        }
+        assert_eq!(
+            KStr::<16>::from_str("\"\x1f/").escape_json::<32>().as_str(),
+            "\\\"\\u001f/"
+        );
+    }
+
+    #[test]
+    fn rust_escapes() {
+        let k = KStr::<32>::from_str("\\x41\\u{1F_980}\\\n   b\\'");
+        assert_eq!(k.unescape_rust::<32>().unwrap().as_str(), "A🦀b'");
+    }
+
+    #[test]
+    fn errors() {
+        let err = |s: &str, json: bool| {
+            let k = KStr::<32>::from_str(s);
+            let res = if json {
+                k.unescape_json::<32>()
+            } else {
+                k.unescape_rust::<32>()
+            };
+            let e = res.unwrap_err();
+            (e.kind, e.offset)
+        };
+        use EscapeErrorKind::*;
+        assert_eq!(err("ab\\q", false), (UnknownEscape, 2));
+        assert_eq!(err("ab\\", false), (UnterminatedEscape, 2));
+        assert_eq!(err("\\x4", false), (UnterminatedEscape, 0));
+        assert_eq!(err("a\\x4g", false), (InvalidHex, 1));
+        assert_eq!(err("\\x80", false), (HexOutOfRange, 0));
+        assert_eq!(err("\\u{d800}", false), (InvalidCodePoint, 0));
+        assert_eq!(err("\\u{1234567}", false), (InvalidHex, 0));
+        assert_eq!(err("é\\ud83e", true), (LoneSurrogate, 2));
+        assert_eq!(err("\\udd80", true), (LoneSurrogate, 0));
+        assert_eq!(err("\\u12", true), (UnterminatedEscape, 0));
+        assert_eq!(err("a\nb", true), (UnescapedControl, 1));
+        assert_eq!(
+            KStr::<8>::from_str("\\q")
+                .unescape_json::<8>()
+                .unwrap_err()
+                .to_string(),
+            "unknown escape at byte 0"
+        );
+    }
+}
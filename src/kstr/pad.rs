@@ -0,0 +1,152 @@
+use super::{char_at, KStr};
+
+/// Alignment of a String padded to a width
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Alignment {
+    /// Fill on the right
+    Left,
+    /// Fill on both sides, the extra fill char goes on the right like `format!("{:^5}")`
+    Center,
+    /// Fill on the left
+    Right,
+}
+
+// Widths are measured in chars, not in bytes or terminal columns.
+impl<const N: usize> KStr<N> {
+    /// Returns a new String of capacity `M` padded on the left with `fill` to `width` chars.
+    /// # Panics: panic if the result doesn't fit in `M`
+    #[must_use]
+    pub const fn pad_left<const M: usize>(&self, width: usize, fill: char) -> KStr<M> {
+        self.align(width, Alignment::Right, fill)
+    }
+    /// Returns a new String of capacity `M` padded on the right with `fill` to `width` chars.
+    /// # Panics: panic if the result doesn't fit in `M`
+    #[must_use]
+    pub const fn pad_right<const M: usize>(&self, width: usize, fill: char) -> KStr<M> {
+        self.align(width, Alignment::Left, fill)
+    }
+    /// Returns a new String of capacity `M` padded on both sides with `fill` to `width` chars.
+    /// # Panics: panic if the result doesn't fit in `M`
+    #[must_use]
+    pub const fn center<const M: usize>(&self, width: usize, fill: char) -> KStr<M> {
+        self.align(width, Alignment::Center, fill)
+    }
+    /// Returns a new String of capacity `M` aligned in `width` chars using `fill`,
+    /// Strings that are already wide enough are copied as is.
+    /// # Panics: panic if the result doesn't fit in `M`
+    #[must_use]
+    pub const fn align<const M: usize>(
+        &self,
+        width: usize,
+        alignment: Alignment,
+        fill: char,
+    ) -> KStr<M> {
+        let count = self.chars_count();
+        let padding = width.saturating_sub(count);
+        let (left, right) = match alignment {
+            Alignment::Left => (0, padding),
+            Alignment::Center => (padding / 2, padding - padding / 2),
+            Alignment::Right => (padding, 0),
+        };
+        let mut out = KStr::new();
+        let mut idx = 0;
+        while idx < left {
+            out = out.push_char(fill);
+            idx += 1;
+        }
+        out = out.extend(self.as_bytes());
+        idx = 0;
+        while idx < right {
+            out = out.push_char(fill);
+            idx += 1;
+        }
+        out
+    }
+    /// Returns a new String holding at most `width` chars.
+    #[must_use]
+    pub const fn truncate_to(mut self, width: usize) -> Self {
+        self.vec.cursor = nth_char_idx(self.as_str(), width);
+        self
+    }
+    /// Returns a new String holding at most `width` chars, Strings that are too wide are cut and end with `ellipsis`.
+    ///
+    /// If `ellipsis` is wider than `width` only its start is kept.
+    /// # Panics: panic if the result doesn't fit in the buffer
+    #[must_use]
+    pub const fn truncate_with_ellipsis(self, width: usize, ellipsis: &str) -> Self {
+        if self.chars_count() <= width {
+            return self;
+        }
+        let ellipsis_width = count_chars(ellipsis);
+        if ellipsis_width >= width {
+            let end = nth_char_idx(ellipsis, width);
+            return Self::new().extend(ellipsis.split_at(end).0.as_bytes());
+        }
+        self.truncate_to(width - ellipsis_width)
+            .extend(ellipsis.as_bytes())
+    }
+}
+
+const fn count_chars(s: &str) -> usize {
+    let mut idx = 0;
+    let mut count = 0;
+    while let Some((_, next)) = char_at(s, idx) {
+        count += 1;
+        idx = next;
+    }
+    count
+}
+
+/// Returns the byte index of the `n`th char, or the length if there are less chars.
+const fn nth_char_idx(s: &str, n: usize) -> usize {
+    let mut idx = 0;
+    let mut count = 0;
+    while count < n {
+        match char_at(s, idx) {
+            Some((_, next)) => idx = next,
+            None => break,
+        }
+        count += 1;
+    }
+    idx
+}
+
+#[cfg(test)]
+mod pad_test {
+    use super::*;
+
+    const S: KStr<8> = KStr::from_str("héllo");
+    const _: () = {
+        assert!(S.pad_left::<16>(8, '·').chars_count() == 8);
+        assert!(S.center::<8>(3, ' ').len() == S.len());
+        assert!(S.truncate_to(2).len() == 3);
+    };
+
+    #[test]
+    fn parity_with_format() {
+        for s in ["", "a", "héllo", "€€"] {
+            let k = KStr::<8>::from_str(s);
+            for width in 0..8 {
+                assert_eq!(
+                    k.pad_left::<32>(width, '*').as_str(),
+                    format!("{s:*>width$}")
+                );
+                assert_eq!(
+                    k.pad_right::<32>(width, '*').as_str(),
+                    format!("{s:*<width$}")
+                );
+                assert_eq!(k.center::<32>(width, '*').as_str(), format!("{s:*^width$}"));
+                assert_eq!(k.truncate_to(width).as_str(), format!("{s:.width$}"));
+            }
+        }
+    }
+
+    #[test]
+    fn ellipsis() {
+        let k = KStr::<16>::from_str("hello world");
+        assert_eq!(k.truncate_with_ellipsis(8, "…").as_str(), "hello w…");
+        assert_eq!(k.truncate_with_ellipsis(8, "...").as_str(), "hello...");
+        assert_eq!(k.truncate_with_ellipsis(2, "...").as_str(), "..");
+        assert_eq!(k.truncate_with_ellipsis(11, "...").as_str(), "hello world");
+    }
+}
@@ -0,0 +1,188 @@
+use super::KStr;
+use crate::error::impl_error;
+
+/// Error returned when parsing a malformed integer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseIntError {
+    /// What is wrong with the integer
+    pub kind: ParseIntErrorKind,
+    /// Byte index of the offending char, or of the digit that made the value overflow
+    pub offset: usize,
+}
+
+/// The different ways an integer can be malformed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseIntErrorKind {
+    /// The String is empty
+    Empty,
+    /// A char that is not a digit, or a sign without digits
+    InvalidDigit,
+    /// The value doesn't fit in the type
+    Overflow,
+}
+
+impl ParseIntErrorKind {
+    /// Returns a description of the error.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            ParseIntErrorKind::Empty => "empty integer",
+            ParseIntErrorKind::InvalidDigit => "invalid digit",
+            ParseIntErrorKind::Overflow => "integer out of range",
+        }
+    }
+}
+
+impl ParseIntError {
+    const fn new(kind: ParseIntErrorKind, offset: usize) -> Self {
+        Self { kind, offset }
+    }
+}
+impl_error!(ParseIntError, " at byte " offset);
+
+// Like `str::parse`, an optional `+` or `-` sign is followed by decimal digits.
+impl<const N: usize> KStr<N> {
+    /// Parses the String into a u64.
+    pub const fn parse_u64(&self) -> Result<u64, ParseIntError> {
+        parse_u64(self.as_str())
+    }
+    /// Parses the String into an i64.
+    pub const fn parse_i64(&self) -> Result<i64, ParseIntError> {
+        parse_i64(self.as_str())
+    }
+    /// Parses the String into a u32.
+    pub const fn parse_u32(&self) -> Result<u32, ParseIntError> {
+        match parse_unsigned(self.as_str(), u32::MAX as u64) {
+            Ok(n) => Ok(n as u32),
+            Err(err) => Err(err),
+        }
+    }
+    /// Parses the String into an i32.
+    pub const fn parse_i32(&self) -> Result<i32, ParseIntError> {
+        match parse_signed(self.as_str(), i32::MAX as u64) {
+            Ok(n) => Ok(n as i32),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+pub(crate) const fn parse_u64(s: &str) -> Result<u64, ParseIntError> {
+    parse_unsigned(s, u64::MAX)
+}
+
+pub(crate) const fn parse_i64(s: &str) -> Result<i64, ParseIntError> {
+    parse_signed(s, i64::MAX as u64)
+}
+
+/// Parses an unsigned integer up to `max`.
+const fn parse_unsigned(s: &str, max: u64) -> Result<u64, ParseIntError> {
+    match s.as_bytes() {
+        [b'-', ..] => Err(ParseIntError::new(ParseIntErrorKind::InvalidDigit, 0)),
+        [b'+', ..] => parse_digits(s.as_bytes(), 1, max),
+        _ => parse_digits(s.as_bytes(), 0, max),
+    }
+}
+
+/// Parses a signed integer from `-max - 1` to `max`, the value is returned as its two's complement.
+const fn parse_signed(s: &str, max: u64) -> Result<i64, ParseIntError> {
+    match s.as_bytes() {
+        [b'-', ..] => match parse_digits(s.as_bytes(), 1, max + 1) {
+            Ok(n) => Ok(0u64.wrapping_sub(n) as i64),
+            Err(err) => Err(err),
+        },
+        [b'+', ..] => match parse_digits(s.as_bytes(), 1, max) {
+            Ok(n) => Ok(n as i64),
+            Err(err) => Err(err),
+        },
+        _ => match parse_digits(s.as_bytes(), 0, max) {
+            Ok(n) => Ok(n as i64),
+            Err(err) => Err(err),
+        },
+    }
+}
+
+/// Parses the decimal digits from `start` to the end, failing if the value goes above `max`.
+const fn parse_digits(bytes: &[u8], start: usize, max: u64) -> Result<u64, ParseIntError> {
+    if bytes.is_empty() {
+        return Err(ParseIntError::new(ParseIntErrorKind::Empty, 0));
+    }
+    if start == bytes.len() {
+        return Err(ParseIntError::new(ParseIntErrorKind::InvalidDigit, 0));
+    }
+    let mut value: u64 = 0;
+    let mut idx = start;
+    while idx < bytes.len() {
+        if !bytes[idx].is_ascii_digit() {
+            return Err(ParseIntError::new(ParseIntErrorKind::InvalidDigit, idx));
+        }
+        value = match value.checked_mul(10) {
+            Some(n) => match n.checked_add((bytes[idx] - b'0') as u64) {
+                Some(n) if n <= max => n,
+                _ => return Err(ParseIntError::new(ParseIntErrorKind::Overflow, idx)),
+            },
+            None => return Err(ParseIntError::new(ParseIntErrorKind::Overflow, idx)),
+        };
+        idx += 1;
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod parse_test {
+    use super::*;
+
+    const _: () = {
+        assert!(matches!(KStr::<8>::from_str("-42").parse_i32(), Ok(-42)));
+        assert!(matches!(KStr::<8>::from_str("+7").parse_u32(), Ok(7)));
+        assert!(KStr::<8>::from_str("1_000").parse_u64().is_err());
+    };
+
+    #[test]
+    fn parity_with_std() {
+        for s in [
+            "",
+            "0",
+            "+",
+            "-",
+            "-0",
+            "+12",
+            "007",
+            " 1",
+            "1a",
+            "4294967295",
+            "4294967296",
+            "-2147483648",
+            "-2147483649",
+            "2147483648",
+            "18446744073709551615",
+            "18446744073709551616",
+            "-9223372036854775808",
+            "-9223372036854775809",
+            "9223372036854775807",
+            "9223372036854775808",
+            "99999999999999999999999",
+        ] {
+            let k = KStr::<32>::from_str(s);
+            assert_eq!(k.parse_u64().ok(), s.parse().ok(), "{s}");
+            assert_eq!(k.parse_i64().ok(), s.parse().ok(), "{s}");
+            assert_eq!(k.parse_u32().ok(), s.parse().ok(), "{s}");
+            assert_eq!(k.parse_i32().ok(), s.parse().ok(), "{s}");
+            if let (Err(err), Err(std)) = (k.parse_i64(), s.parse::<i64>()) {
+                let std_kind = match std.kind() {
+                    std::num::IntErrorKind::Empty => ParseIntErrorKind::Empty,
+                    std::num::IntErrorKind::InvalidDigit => ParseIntErrorKind::InvalidDigit,
+                    _ => ParseIntErrorKind::Overflow,
+                };
+                assert_eq!(err.kind, std_kind, "{s}");
+            }
+        }
+    }
+
+    #[test]
+    fn offsets() {
+        let err = KStr::<32>::from_str("12x4").parse_u64().unwrap_err();
+        assert_eq!(err, ParseIntError::new(ParseIntErrorKind::InvalidDigit, 2));
+        let err = KStr::<32>::from_str("-2147483649").parse_i32().unwrap_err();
+        assert_eq!(err, ParseIntError::new(ParseIntErrorKind::Overflow, 10));
+        assert_eq!(err.to_string(), "integer out of range at byte 10");
+    }
+}
@@ -0,0 +1,364 @@
+use super::KStr;
+
+/// Pattern that can be searched for in a [`KStr`]
+///
+/// Traits can't be used in const context, so a byte, a char, a `&str` or a [`KStr`] are
+/// converted to a Pattern with one of its const constructors instead.
+///
+/// ```rust
+/// use konster::kstr::{KStr, Pattern};
+///
+/// const S: KStr<20> = KStr::from_str("on_red.bold");
+/// const _: () = assert!(S.starts_with(Pattern::str("on_")));
+/// const _: () = assert!(matches!(S.find(Pattern::char('.')), Some(6)));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Pattern<'a> {
+    repr: Repr<'a>,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Repr<'a> {
+    Inline([u8; 4], usize),
+    Slice(&'a [u8]),
+}
+
+impl<'a> Pattern<'a> {
+    /// Pattern matching an ASCII byte
+    /// # Panics: panic if the byte is not ASCII, since it could match inside a char
+    pub const fn byte(b: u8) -> Self {
+        if !b.is_ascii() {
+            panic!("Can not search for a non ASCII byte, use Pattern::char instead");
+        }
+        Self {
+            repr: Repr::Inline([b, 0, 0, 0], 1),
+        }
+    }
+    /// Pattern matching a char
+    pub const fn char(c: char) -> Self {
+        let mut bytes = [0; 4];
+        let len = c.encode_utf8(&mut bytes).len();
+        Self {
+            repr: Repr::Inline(bytes, len),
+        }
+    }
+    /// Pattern matching a [`std::primitive::str`]
+    pub const fn str(s: &'a str) -> Self {
+        Self {
+            repr: Repr::Slice(s.as_bytes()),
+        }
+    }
+    /// Pattern matching a [`KStr`]
+    pub const fn kstr<const N: usize>(s: &'a KStr<N>) -> Self {
+        Self {
+            repr: Repr::Slice(s.as_bytes()),
+        }
+    }
+    /// Returns the UTF-8 bytes that the Pattern matches.
+    pub const fn as_bytes(&self) -> &[u8] {
+        match &self.repr {
+            Repr::Inline(bytes, len) => bytes.split_at(*len).0,
+            Repr::Slice(bytes) => bytes,
+        }
+    }
+    /// Returns the length of the Pattern in bytes.
+    pub const fn len(&self) -> usize {
+        self.as_bytes().len()
+    }
+    /// Returns true if the Pattern is the empty string, which matches at every char boundary.
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl From<u8> for Pattern<'_> {
+    fn from(b: u8) -> Self {
+        Self::byte(b)
+    }
+}
+impl From<char> for Pattern<'_> {
+    fn from(c: char) -> Self {
+        Self::char(c)
+    }
+}
+impl<'a> From<&'a str> for Pattern<'a> {
+    fn from(s: &'a str) -> Self {
+        Self::str(s)
+    }
+}
+impl<'a, const N: usize> From<&'a KStr<N>> for Pattern<'a> {
+    fn from(s: &'a KStr<N>) -> Self {
+        Self::kstr(s)
+    }
+}
+
+impl<const N: usize> KStr<N> {
+    /// Returns the byte index of the first match of the pattern, or None if it doesn't match.
+    pub const fn find(&self, pat: Pattern) -> Option<usize> {
+        find_bytes(self.as_bytes(), pat.as_bytes(), 0)
+    }
+    /// Returns the byte index of the start of the last match of the pattern, or None if it doesn't match.
+    pub const fn rfind(&self, pat: Pattern) -> Option<usize> {
+        rfind_bytes(self.as_bytes(), pat.as_bytes(), self.len())
+    }
+    /// Returns true if the pattern matches somewhere in the String.
+    pub const fn contains(&self, pat: Pattern) -> bool {
+        self.find(pat).is_some()
+    }
+    /// Returns true if the String starts with the pattern.
+    pub const fn starts_with(&self, pat: Pattern) -> bool {
+        starts_with_bytes(self.as_bytes(), pat.as_bytes())
+    }
+    /// Returns true if the String ends with the pattern.
+    pub const fn ends_with(&self, pat: Pattern) -> bool {
+        let (bytes, pat) = (self.as_bytes(), pat.as_bytes());
+        bytes.len() >= pat.len() && bytes_eq(bytes.split_at(bytes.len() - pat.len()).1, pat)
+    }
+    /// Returns the number of non overlapping matches of the pattern.
+    ///
+    /// Like [`str::matches`], the empty pattern matches at every char boundary.
+    pub const fn matches_count(&self, pat: Pattern) -> usize {
+        let (bytes, pat) = (self.as_bytes(), pat.as_bytes());
+        if pat.is_empty() {
+            return self.chars_count() + 1;
+        }
+        let searcher = TwoWay::new(pat, false);
+        let mut count = 0;
+        let mut from = 0;
+        while let Some(idx) = searcher.search(bytes, pat, from) {
+            count += 1;
+            from = idx + pat.len();
+        }
+        count
+    }
+}
+
+/// Returns the index of the first match of needle in `hay[from..]`.
+pub(crate) const fn find_bytes(hay: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    TwoWay::new(needle, false).search(hay, needle, from)
+}
+
+/// Returns the index of the start of the last match of needle in `hay[..to]`.
+pub(crate) const fn rfind_bytes(hay: &[u8], needle: &[u8], to: usize) -> Option<usize> {
+    TwoWay::new(needle, true).search(hay.split_at(to).0, needle, 0)
+}
+
+pub(crate) const fn starts_with_bytes(bytes: &[u8], pat: &[u8]) -> bool {
+    bytes.len() >= pat.len() && bytes_eq(bytes.split_at(pat.len()).0, pat)
+}
+
+pub(crate) const fn bytes_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut idx = 0;
+    while idx < a.len() {
+        if a[idx] != b[idx] {
+            return false;
+        }
+        idx += 1;
+    }
+    true
+}
+
+/// Reads the slice front to back, or back to front when `rev` is set.
+const fn at(bytes: &[u8], idx: usize, rev: bool) -> u8 {
+    if rev {
+        bytes[bytes.len() - 1 - idx]
+    } else {
+        bytes[idx]
+    }
+}
+
+/// Two-way string matching (Crochemore-Perrin), it runs in linear time and constant space
+/// which keeps big haystacks within the const evaluation limits.
+///
+/// When `rev` is set both the needle and the haystack are read back to front, so the
+/// first match found is the last one in the haystack.
+#[derive(Clone, Copy)]
+struct TwoWay {
+    crit_pos: usize,
+    period: usize,
+    long_period: bool,
+    rev: bool,
+}
+
+impl TwoWay {
+    const fn new(needle: &[u8], rev: bool) -> Self {
+        let (crit_pos_lt, period_lt) = maximal_suffix(needle, false, rev);
+        let (crit_pos_gt, period_gt) = maximal_suffix(needle, true, rev);
+        let (crit_pos, period) = if crit_pos_lt > crit_pos_gt {
+            (crit_pos_lt, period_lt)
+        } else {
+            (crit_pos_gt, period_gt)
+        };
+
+        // The needle is periodic when the part left of the critical position
+        // repeats one period later.
+        let mut periodic = period + crit_pos <= needle.len();
+        let mut idx = 0;
+        while periodic && idx < crit_pos {
+            periodic = at(needle, idx, rev) == at(needle, period + idx, rev);
+            idx += 1;
+        }
+
+        if periodic {
+            Self {
+                crit_pos,
+                period,
+                long_period: false,
+                rev,
+            }
+        } else {
+            let left = crit_pos;
+            let right = needle.len() - crit_pos;
+            Self {
+                crit_pos,
+                period: if left > right { left } else { right } + 1,
+                long_period: true,
+                rev,
+            }
+        }
+    }
+
+    /// Returns the index (counted from the front of hay) of the first match starting at or
+    /// after `from`, or the last match when searching in reverse.
+    const fn search(&self, hay: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+        let (m, n) = (needle.len(), hay.len());
+        let mut position = from;
+        let mut memory = 0;
+        while position + m <= n {
+            let mut idx = if !self.long_period && memory > self.crit_pos {
+                memory
+            } else {
+                self.crit_pos
+            };
+            while idx < m && at(needle, idx, self.rev) == at(hay, position + idx, self.rev) {
+                idx += 1;
+            }
+            if idx < m {
+                position += idx - self.crit_pos + 1;
+                memory = 0;
+                continue;
+            }
+
+            let start = if self.long_period { 0 } else { memory };
+            let mut idx = self.crit_pos;
+            while idx > start
+                && at(needle, idx - 1, self.rev) == at(hay, position + idx - 1, self.rev)
+            {
+                idx -= 1;
+            }
+            if idx <= start {
+                return Some(if self.rev { n - position - m } else { position });
+            }
+            position += self.period;
+            if !self.long_period {
+                memory = m - self.period;
+            }
+        }
+        None
+    }
+}
+
+/// Returns the start and period of the maximal suffix of the needle, for the byte order
+/// selected by `greater`.
+const fn maximal_suffix(needle: &[u8], greater: bool, rev: bool) -> (usize, usize) {
+    let mut left = 0;
+    let mut right = 1;
+    let mut offset = 0;
+    let mut period = 1;
+    while right + offset < needle.len() {
+        let a = at(needle, right + offset, rev);
+        let b = at(needle, left + offset, rev);
+        if (a < b && !greater) || (a > b && greater) {
+            right += offset + 1;
+            offset = 0;
+            period = right - left;
+        } else if a == b {
+            if offset + 1 == period {
+                right += offset + 1;
+                offset = 0;
+            } else {
+                offset += 1;
+            }
+        } else {
+            left = right;
+            right += 1;
+            offset = 0;
+            period = 1;
+        }
+    }
+    (left, period)
+}
+
+#[cfg(test)]
+mod pattern_test {
+    use super::*;
+
+    const S: KStr<40> = KStr::from_str("héllo wörld, hello world");
+    const _: () = {
+        assert!(matches!(S.find(Pattern::str("world")), Some(21)));
+        assert!(matches!(S.rfind(Pattern::char('o')), Some(22)));
+        assert!(matches!(S.find(Pattern::char('ö')), Some(8)));
+        assert!(S.contains(Pattern::kstr(&KStr::<5>::from_str("llo"))));
+        assert!(!S.contains(Pattern::str("worlds")));
+        assert!(S.starts_with(Pattern::str("hé")));
+        assert!(S.ends_with(Pattern::byte(b'd')));
+        assert!(S.matches_count(Pattern::str("l")) == 6);
+    };
+
+    fn check(hay: &str, needle: &str) {
+        let s = KStr::<64>::from_str(hay);
+        assert_eq!(
+            s.find(needle.into()),
+            hay.find(needle),
+            "{hay:?} {needle:?}"
+        );
+        assert_eq!(
+            s.rfind(needle.into()),
+            hay.rfind(needle),
+            "{hay:?} {needle:?}"
+        );
+        assert_eq!(
+            s.matches_count(needle.into()),
+            hay.matches(needle).count(),
+            "{hay:?} {needle:?}"
+        );
+    }
+
+    #[test]
+    fn parity_with_std() {
+        let hays = [
+            "",
+            "a",
+            "aaaaab",
+            "abababab",
+            "abcabcabd",
+            "banana",
+            "mississippi",
+            "ab€ab€",
+        ];
+        let needles = [
+            "", "a", "aa", "ab", "aab", "aba", "abab", "ana", "issi", "ssippi", "€a", "zz",
+        ];
+        for hay in hays {
+            for needle in needles {
+                check(hay, needle);
+            }
+        }
+    }
+
+    #[test]
+    fn large_haystack_in_const() {
+        const HAY: KStr<4096> = {
+            let mut s = KStr::new();
+            while s.len() < 4000 {
+                s = s.push(b'a');
+            }
+            s.push_str(&KStr::from_str("ab"))
+        };
+        const IDX: Option<usize> = HAY.find(Pattern::str("aaaaaaaaab"));
+        assert_eq!(IDX, Some(3992));
+    }
+}
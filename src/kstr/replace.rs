@@ -0,0 +1,143 @@
+use super::trim::slice;
+use super::{KStr, Pattern, SplitCursor};
+
+impl<const N: usize> KStr<N> {
+    /// Returns a new String of capacity `M` with all matches of the pattern replaced by `to`.
+    ///
+    /// Like [`str::replace`], the empty pattern matches at every char boundary.
+    /// # Panics: panic if the result doesn't fit in `M`
+    #[must_use]
+    pub const fn replace<const M: usize>(&self, from: Pattern, to: &str) -> KStr<M> {
+        join(SplitCursor::new(self.as_str(), from), to)
+    }
+    /// Returns a new String of capacity `M` with the first `count` matches of the pattern replaced by `to`.
+    /// # Panics: panic if the result doesn't fit in `M`
+    #[must_use]
+    pub const fn replacen<const M: usize>(&self, from: Pattern, to: &str, count: usize) -> KStr<M> {
+        join(
+            SplitCursor::new(self.as_str(), from).limit(count.saturating_add(1)),
+            to,
+        )
+    }
+    /// Returns a new String with all occurrences of the ASCII byte `from` replaced by `to`.
+    /// # Panics: panic if either byte is not ASCII
+    #[must_use]
+    pub const fn replace_byte(mut self, from: u8, to: u8) -> Self {
+        if !from.is_ascii() || !to.is_ascii() {
+            panic!("Can not replace non ASCII bytes, use replace instead");
+        }
+        let mut idx = 0;
+        while idx < self.len() {
+            if self.vec.buf[idx] == from {
+                self.vec.buf[idx] = to;
+            }
+            idx += 1;
+        }
+        self
+    }
+    /// Returns a new String of capacity `M` holding the String repeated `n` times.
+    /// # Panics: panic if the result doesn't fit in `M`
+    #[must_use]
+    pub const fn repeat<const M: usize>(&self, n: usize) -> KStr<M> {
+        let mut out = KStr::new();
+        let mut count = 0;
+        while count < n {
+            out = out.extend(self.as_bytes());
+            count += 1;
+        }
+        out
+    }
+    /// Returns a new String with `s` inserted at the byte index.
+    /// # Panics: panic if the index is out of bounds or not on a char boundary, or if the buffer is full
+    #[must_use]
+    pub const fn insert_str(self, idx: usize, s: &str) -> Self {
+        let (head, tail) = match (
+            slice(self.as_str(), 0, idx),
+            slice(self.as_str(), idx, self.len()),
+        ) {
+            (Some(head), Some(tail)) => (head, tail),
+            _ => panic!("Insertion index is out of bounds or not on a char boundary"),
+        };
+        Self::new()
+            .extend(head.as_bytes())
+            .extend(s.as_bytes())
+            .extend(tail.as_bytes())
+    }
+    /// Returns a new String without the bytes between the indices.
+    /// # Panics: panic if the indices are out of bounds or not on char boundaries
+    #[must_use]
+    pub const fn remove_range(self, start: usize, end: usize) -> Self {
+        let (head, tail) = match (
+            slice(self.as_str(), 0, start),
+            slice(self.as_str(), end, self.len()),
+        ) {
+            (Some(head), Some(tail)) if start <= end => (head, tail),
+            _ => panic!("Range is out of bounds or not on a char boundary"),
+        };
+        Self::new().extend(head.as_bytes()).extend(tail.as_bytes())
+    }
+}
+
+/// Joins the parts yielded by the cursor with `sep`.
+const fn join<const M: usize>(mut cursor: SplitCursor, sep: &str) -> KStr<M> {
+    let mut out = KStr::new();
+    let mut first = true;
+    while let Some((next, part)) = cursor.next() {
+        if !first {
+            out = out.extend(sep.as_bytes());
+        }
+        out = out.extend(part.as_bytes());
+        first = false;
+        cursor = next;
+    }
+    out
+}
+
+#[cfg(test)]
+mod replace_test {
+    use super::*;
+
+    const S: KStr<16> = KStr::from_str("a-b-c");
+    const REPLACED: KStr<16> = S.replace(Pattern::char('-'), "::");
+    const _: () = {
+        assert!(REPLACED.len() == 7);
+        assert!(S.replace_byte(b'-', b'+').ends_with(Pattern::str("+c")));
+        assert!(S.repeat::<15>(3).len() == 15);
+        assert!(S
+            .insert_str(1, "é")
+            .remove_range(1, 3)
+            .eq_ignore_ascii_case("A-B-C"));
+    };
+
+    #[test]
+    fn parity_with_std() {
+        for s in ["", "aaa", "ab€ab", "banana"] {
+            let k = KStr::<16>::from_str(s);
+            for (from, to) in [("a", "xy"), ("", "-"), ("an", ""), ("€", "e")] {
+                assert_eq!(
+                    k.replace::<64>(from.into(), to).as_str(),
+                    s.replace(from, to)
+                );
+                for n in 0..3 {
+                    assert_eq!(
+                        k.replacen::<64>(from.into(), to, n).as_str(),
+                        s.replacen(from, to, n)
+                    );
+                }
+            }
+        }
+        let mut std = String::from("héllo");
+        std.insert_str(3, "üx");
+        std.replace_range(0..1, "");
+        let k = KStr::<16>::from_str("héllo")
+            .insert_str(3, "üx")
+            .remove_range(0, 1);
+        assert_eq!(k.as_str(), std);
+    }
+
+    #[test]
+    #[should_panic]
+    fn overflow() {
+        let _ = S.repeat::<14>(3);
+    }
+}
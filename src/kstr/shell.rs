@@ -0,0 +1,219 @@
+use super::{char_at, push_to_lines_vec, KStr};
+use crate::error::impl_error;
+use crate::kvec::KVec;
+
+/// Error returned when splitting a malformed command line
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShellSplitError {
+    /// What is wrong with the command line
+    pub kind: ShellSplitErrorKind,
+    /// Byte index of the opening quote or of the backslash
+    pub offset: usize,
+}
+
+/// The different ways a command line can be malformed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShellSplitErrorKind {
+    /// A `'` without its closing quote
+    UnterminatedSingleQuote,
+    /// A `"` without its closing quote
+    UnterminatedDoubleQuote,
+    /// A `\` at the end of the String
+    TrailingBackslash,
+}
+
+impl ShellSplitErrorKind {
+    /// Returns a description of the error.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            ShellSplitErrorKind::UnterminatedSingleQuote => "unterminated single quote",
+            ShellSplitErrorKind::UnterminatedDoubleQuote => "unterminated double quote",
+            ShellSplitErrorKind::TrailingBackslash => "trailing backslash",
+        }
+    }
+}
+
+impl ShellSplitError {
+    const fn new(kind: ShellSplitErrorKind, offset: usize) -> Self {
+        Self { kind, offset }
+    }
+}
+impl_error!(ShellSplitError, " at byte " offset);
+
+impl<const N: usize> KStr<N> {
+    /// Returns a Vector of the words of the String, split like a POSIX shell does.
+    ///
+    /// Words are separated by unquoted spaces, tabs and newlines. Single quotes keep everything
+    /// literally, double quotes only let `\` escape `$`, `` ` ``, `"`, `\` and newlines, and an
+    /// unquoted `\` escapes any char. An escaped newline is removed, and a `#` at the start of a
+    /// word comments out the rest of the line. No expansion is done, `$HOME` stays as is.
+    /// # Panics: panic if the Vector or one of the Strings is too small
+    pub const fn shell_split<const L: usize, const NN: usize>(
+        &self,
+    ) -> Result<KVec<KStr<NN>, L>, ShellSplitError> {
+        let s = self.as_str();
+        let mut words = KVec::new(KStr::new());
+        let mut word = KStr::<NN>::new();
+        // Quotes make a word even when they are empty
+        let mut in_word = false;
+        let mut idx = 0;
+        while let Some((c, next)) = char_at(s, idx) {
+            match c {
+                ' ' | '\t' | '\n' => {
+                    if in_word {
+                        words = push_to_lines_vec(words, word);
+                        word = KStr::new();
+                        in_word = false;
+                    }
+                    idx = next;
+                }
+                '#' if !in_word => {
+                    idx = next;
+                    while let Some((c, next)) = char_at(s, idx) {
+                        if c == '\n' {
+                            break;
+                        }
+                        idx = next;
+                    }
+                }
+                '\'' => {
+                    let mut end = next;
+                    loop {
+                        match char_at(s, end) {
+                            Some(('\'', _)) => break,
+                            Some((_, after)) => end = after,
+                            None => {
+                                return Err(ShellSplitError::new(
+                                    ShellSplitErrorKind::UnterminatedSingleQuote,
+                                    idx,
+                                ))
+                            }
+                        }
+                    }
+                    word = word.extend(s.as_bytes().split_at(end).0.split_at(next).1);
+                    in_word = true;
+                    idx = end + 1;
+                }
+                '"' => {
+                    let open = idx;
+                    idx = next;
+                    loop {
+                        match char_at(s, idx) {
+                            Some(('"', next)) => {
+                                idx = next;
+                                break;
+                            }
+                            Some(('\\', next)) => match char_at(s, next) {
+                                Some(('\n', after)) => idx = after,
+                                Some((c @ ('$' | '`' | '"' | '\\'), after)) => {
+                                    word = word.push_char(c);
+                                    idx = after;
+                                }
+                                _ => {
+                                    word = word.push(b'\\');
+                                    idx = next;
+                                }
+                            },
+                            Some((c, next)) => {
+                                word = word.push_char(c);
+                                idx = next;
+                            }
+                            None => {
+                                return Err(ShellSplitError::new(
+                                    ShellSplitErrorKind::UnterminatedDoubleQuote,
+                                    open,
+                                ))
+                            }
+                        }
+                    }
+                    in_word = true;
+                }
+                '\\' => match char_at(s, next) {
+                    Some(('\n', after)) => idx = after,
+                    Some((c, after)) => {
+                        word = word.push_char(c);
+                        in_word = true;
+                        idx = after;
+                    }
+                    None => {
+                        return Err(ShellSplitError::new(
+                            ShellSplitErrorKind::TrailingBackslash,
+                            idx,
+                        ))
+                    }
+                },
+                c => {
+                    word = word.push_char(c);
+                    in_word = true;
+                    idx = next;
+                }
+            }
+        }
+        if in_word {
+            words = push_to_lines_vec(words, word);
+        }
+        Ok(words)
+    }
+}
+
+#[cfg(test)]
+mod shell_test {
+    use super::*;
+
+    const CMD: KStr<64> = KStr::from_str(r#"run --name "two words" 'x y'"#);
+    const _: () = {
+        let words: KVec<KStr<16>, 4> = match CMD.shell_split() {
+            Ok(words) => words,
+            Err(err) => err.panic(),
+        };
+        assert!(words.len() == 4);
+        assert!(words.buf[2].len() == 9);
+    };
+
+    fn split(s: &str) -> Result<Vec<String>, ShellSplitError> {
+        KStr::<64>::from_str(s)
+            .shell_split::<8, 32>()
+            .map(|words| words.as_slice().iter().map(|w| w.as_str().into()).collect())
+    }
+
+    #[test]
+    fn words() {
+        for (s, expected) in [
+            ("", &[][..]),
+            ("  \t\n ", &[]),
+            ("a b\tc\nd", &["a", "b", "c", "d"]),
+            ("'' \"\"", &["", ""]),
+            ("a'b c'd", &["ab cd"]),
+            (r"'a\b' 'it'\''s'", &[r"a\b", "it's"]),
+            (r#""a\"b" "\$x \n" "\\""#, &["a\"b", r"$x \n", "\\"]),
+            ("\"a\\\nb\" c\\\nd", &["ab", "cd"]),
+            (r"a\ b \'c", &["a b", "'c"]),
+            ("a #comment\nb c#d", &["a", "b", "c#d"]),
+            ("é 'ü x'", &["é", "ü x"]),
+            ("$HOME ~ *", &["$HOME", "~", "*"]),
+        ] {
+            assert_eq!(split(s).unwrap(), expected, "{s:?}");
+        }
+    }
+
+    #[test]
+    fn errors() {
+        for (s, kind, offset) in [
+            ("a 'b", ShellSplitErrorKind::UnterminatedSingleQuote, 2),
+            ("a \"b\\\"", ShellSplitErrorKind::UnterminatedDoubleQuote, 2),
+            ("ab\\", ShellSplitErrorKind::TrailingBackslash, 2),
+        ] {
+            assert_eq!(split(s), Err(ShellSplitError::new(kind, offset)), "{s:?}");
+        }
+        assert_eq!(
+            ShellSplitError::new(ShellSplitErrorKind::TrailingBackslash, 2).to_string(),
+            "trailing backslash at byte 2"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_many_words() {
+        let _ = KStr::<8>::from_str("a b c").shell_split::<2, 1>();
+    }
+}
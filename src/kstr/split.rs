@@ -0,0 +1,630 @@
+use super::pattern::{find_bytes, rfind_bytes};
+use super::trim::slice;
+use super::{char_at, prev_char_boundary, push_to_lines_vec, KStr, Pattern};
+use crate::kvec::KVec;
+
+// The methods returning a Vector need the backing buffer length of the Vector, and the
+// backing buffer length for each string, to be specified as const generics.
+// They panic if the Vector or one of the Strings is too small.
+impl<const N: usize> KStr<N> {
+    /// Returns a Vector of the Strings by splitting the string on the specified byte,
+    /// a byte at the end of the String doesn't result in an empty String.
+    ///
+    /// Use [`KStr::split_pattern`] to split on a [`Pattern`] like [`str::split`].
+    /// # Panics: panic if split is not ASCII
+    pub const fn split<const L: usize, const NN: usize>(&self, split: u8) -> KVec<KStr<NN>, L> {
+        if !split.is_ascii() {
+            panic!("Can not split on a non ASCII byte");
+        }
+        self.split_terminator(Pattern::byte(split))
+    }
+    /// Returns a Vector of the Strings by splitting the string on the pattern
+    ///
+    /// Like [`str::split`], a pattern at the start or the end of the String results in an empty String.
+    pub const fn split_pattern<const L: usize, const NN: usize>(
+        &self,
+        pat: Pattern,
+    ) -> KVec<KStr<NN>, L> {
+        collect(SplitCursor::new(self.as_str(), pat))
+    }
+    /// Returns a Vector of the Strings by splitting the string on the pattern, starting from the end.
+    pub const fn rsplit<const L: usize, const NN: usize>(&self, pat: Pattern) -> KVec<KStr<NN>, L> {
+        collect(SplitCursor::new(self.as_str(), pat).reversed())
+    }
+    /// Returns a Vector of at most `n` Strings by splitting the string on the pattern,
+    /// the last String contains the remainder.
+    pub const fn splitn<const L: usize, const NN: usize>(
+        &self,
+        n: usize,
+        pat: Pattern,
+    ) -> KVec<KStr<NN>, L> {
+        collect(SplitCursor::new(self.as_str(), pat).limit(n))
+    }
+    /// Returns a Vector of at most `n` Strings by splitting the string on the pattern starting
+    /// from the end, the last String contains the remainder.
+    pub const fn rsplitn<const L: usize, const NN: usize>(
+        &self,
+        n: usize,
+        pat: Pattern,
+    ) -> KVec<KStr<NN>, L> {
+        collect(SplitCursor::new(self.as_str(), pat).reversed().limit(n))
+    }
+    /// Returns a Vector of the Strings by splitting the string on the pattern,
+    /// a pattern at the end of the String doesn't result in an empty String.
+    pub const fn split_terminator<const L: usize, const NN: usize>(
+        &self,
+        pat: Pattern,
+    ) -> KVec<KStr<NN>, L> {
+        collect(SplitCursor::new(self.as_str(), pat).terminator())
+    }
+    /// Returns a Vector of the Strings by splitting the string on the pattern starting from
+    /// the end, a pattern at the end of the String doesn't result in an empty String.
+    pub const fn rsplit_terminator<const L: usize, const NN: usize>(
+        &self,
+        pat: Pattern,
+    ) -> KVec<KStr<NN>, L> {
+        collect(SplitCursor::new(self.as_str(), pat).reversed().terminator())
+    }
+    /// Returns a Vector of the Strings by splitting the string on the pattern,
+    /// each String keeps the pattern that ended it.
+    pub const fn split_inclusive<const L: usize, const NN: usize>(
+        &self,
+        pat: Pattern,
+    ) -> KVec<KStr<NN>, L> {
+        collect(SplitCursor::new(self.as_str(), pat).inclusive())
+    }
+    /// Returns a Vector of the Strings, by splitting the original String by white space.
+    ///
+    /// Like [`str::split_whitespace`], white space is any Unicode White_Space char and the Strings are never empty.
+    pub const fn split_whitespace<const L: usize, const NN: usize>(&self) -> KVec<KStr<NN>, L> {
+        collect(self.split_whitespace_cursor())
+    }
+    /// Returns a Vector of the Strings, by splitting the original String by ASCII white space.
+    ///
+    /// Like [`str::split_ascii_whitespace`], the Strings are never empty.
+    pub const fn split_ascii_whitespace<const L: usize, const NN: usize>(
+        &self,
+    ) -> KVec<KStr<NN>, L> {
+        collect(self.split_ascii_whitespace_cursor())
+    }
+    /// Returns a Vector of the String lines
+    ///
+    /// Like [`str::lines`], lines end with either `\n` or `\r\n` and the final line ending is optional.
+    pub const fn lines<const L: usize, const NN: usize>(&self) -> KVec<KStr<NN>, L> {
+        collect(self.lines_cursor())
+    }
+    /// Returns a cursor over the parts of the String between the matches of the pattern,
+    /// without copying them in a Vector.
+    pub const fn split_cursor<'p>(&self, pat: Pattern<'p>) -> SplitCursor<'_, 'p> {
+        SplitCursor::new(self.as_str(), pat)
+    }
+    /// Returns a cursor over the parts of the String separated by white space,
+    /// without copying them in a Vector.
+    pub const fn split_whitespace_cursor(&self) -> SplitCursor<'_, 'static> {
+        SplitCursor::whitespace(self.as_str())
+    }
+    /// Returns a cursor over the parts of the String separated by ASCII white space,
+    /// without copying them in a Vector.
+    pub const fn split_ascii_whitespace_cursor(&self) -> SplitCursor<'_, 'static> {
+        SplitCursor::ascii_whitespace(self.as_str())
+    }
+    /// Returns a cursor over the String lines, without copying them in a Vector.
+    pub const fn lines_cursor(&self) -> SplitCursor<'_, 'static> {
+        SplitCursor::lines(self.as_str())
+    }
+    /// Returns the String slices before and after the first match of the pattern,
+    /// or None if it doesn't match.
+    pub const fn split_once(&self, pat: Pattern) -> Option<(&str, &str)> {
+        split_once(self.as_str(), pat)
+    }
+    /// Returns the String slices before and after the last match of the pattern,
+    /// or None if it doesn't match.
+    pub const fn rsplit_once(&self, pat: Pattern) -> Option<(&str, &str)> {
+        rsplit_once(self.as_str(), pat)
+    }
+}
+
+pub(crate) const fn split_once<'a>(s: &'a str, pat: Pattern) -> Option<(&'a str, &'a str)> {
+    match find_bytes(s.as_bytes(), pat.as_bytes(), 0) {
+        Some(idx) => Some((s.split_at(idx).0, s.split_at(idx + pat.len()).1)),
+        None => None,
+    }
+}
+
+pub(crate) const fn rsplit_once<'a>(s: &'a str, pat: Pattern) -> Option<(&'a str, &'a str)> {
+    match rfind_bytes(s.as_bytes(), pat.as_bytes(), s.len()) {
+        Some(idx) => Some((s.split_at(idx).0, s.split_at(idx + pat.len()).1)),
+        None => None,
+    }
+}
+
+const fn collect<const L: usize, const NN: usize>(mut split: SplitCursor) -> KVec<KStr<NN>, L> {
+    let mut vec = KVec {
+        buf: [KStr::<NN>::new(); L],
+        cursor: 0,
+    };
+    while let Some((next, piece)) = split.next() {
+        vec = push_to_lines_vec(vec, KStr::from_str(piece));
+        split = next;
+    }
+    vec
+}
+
+/// Cursor over the parts of a String between the matches of a [`Pattern`]
+///
+/// Unlike [`KStr::split`] it doesn't copy the parts in a Vector, it yields slices of the original String one at a time.
+/// Use [`SplitCursor::next`] in const context, or it as an [`Iterator`] at runtime.
+///
+/// ```rust
+/// use konster::kstr::{Pattern, SplitCursor};
+///
+/// const SUM: usize = {
+///     let mut cursor = SplitCursor::new("1,22,3", Pattern::byte(b','));
+///     let mut sum = 0;
+///     while let Some((next, part)) = cursor.next() {
+///         sum += part.len();
+///         cursor = next;
+///     }
+///     sum
+/// };
+/// assert_eq!(SUM, 4);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct SplitCursor<'a, 'p> {
+    s: &'a str,
+    sep: Separator<'p>,
+    /// Byte indices of the part of the String that is not yielded yet
+    start: usize,
+    end: usize,
+    /// Where the next search starts, or ends when searching in reverse
+    pos: usize,
+    searching: bool,
+    finished: bool,
+    allow_trailing_empty: bool,
+    inclusive: bool,
+    skip_empty: bool,
+    strip_line_ending: bool,
+    rev: bool,
+    /// How many parts can still be yielded, the last one holds the remainder
+    remaining: usize,
+}
+
+impl<'a, 'p> SplitCursor<'a, 'p> {
+    /// Creates a cursor over the parts of the String between the matches of the pattern,
+    /// yielding the same parts as [`str::split`].
+    pub const fn new(s: &'a str, pat: Pattern<'p>) -> Self {
+        Self::with_separator(s, Separator::Pattern(pat))
+    }
+    /// Creates a cursor over the parts of the String separated by white space,
+    /// yielding the same parts as [`str::split_whitespace`].
+    pub const fn whitespace(s: &'a str) -> Self {
+        Self::with_separator(s, Separator::Whitespace).skip_empty()
+    }
+    /// Creates a cursor over the parts of the String separated by ASCII white space,
+    /// yielding the same parts as [`str::split_ascii_whitespace`].
+    pub const fn ascii_whitespace(s: &'a str) -> Self {
+        Self::with_separator(s, Separator::AsciiWhitespace).skip_empty()
+    }
+    /// Creates a cursor over the String lines, yielding the same parts as [`str::lines`].
+    pub const fn lines(s: &'a str) -> Self {
+        let mut cursor = Self::new(s, Pattern::byte(b'\n')).inclusive();
+        cursor.strip_line_ending = true;
+        cursor
+    }
+    const fn with_separator(s: &'a str, sep: Separator<'p>) -> Self {
+        Self {
+            s,
+            sep,
+            start: 0,
+            end: s.len(),
+            pos: 0,
+            searching: true,
+            finished: false,
+            allow_trailing_empty: true,
+            inclusive: false,
+            skip_empty: false,
+            strip_line_ending: false,
+            rev: false,
+            remaining: usize::MAX,
+        }
+    }
+    /// Returns a cursor that yields the parts starting from the end, like [`str::rsplit`].
+    #[must_use]
+    pub const fn reversed(mut self) -> Self {
+        self.rev = true;
+        self.pos = self.end;
+        self
+    }
+    /// Returns a cursor that yields at most `n` parts, the last part holds the remainder of the String.
+    #[must_use]
+    pub const fn limit(mut self, n: usize) -> Self {
+        self.remaining = n;
+        self
+    }
+    /// Returns a cursor that doesn't yield an empty part for a pattern at the end of the String.
+    #[must_use]
+    pub const fn terminator(mut self) -> Self {
+        self.allow_trailing_empty = false;
+        self
+    }
+    /// Returns a cursor whose parts keep the pattern that ended them.
+    #[must_use]
+    pub const fn inclusive(mut self) -> Self {
+        self.allow_trailing_empty = false;
+        self.inclusive = true;
+        self
+    }
+    /// Returns a cursor that doesn't yield empty parts.
+    #[must_use]
+    pub const fn skip_empty(mut self) -> Self {
+        self.skip_empty = true;
+        self
+    }
+    /// Returns the part of the String that is not yielded yet.
+    pub const fn remainder(&self) -> &'a str {
+        if self.finished {
+            ""
+        } else {
+            self.piece(self.start, self.end)
+        }
+    }
+
+    /// Returns an option of a tuple of:
+    /// - the advanced cursor
+    /// - the next part of the String
+    ///
+    /// Returns None when all parts are consumed.
+    #[allow(clippy::should_implement_trait)]
+    pub const fn next(mut self) -> Option<(Self, &'a str)> {
+        loop {
+            match self.next_part() {
+                Some((next, part)) if self.skip_empty && part.is_empty() => self = next,
+                Some((next, part)) if self.strip_line_ending => {
+                    return Some((next, strip_line_ending(part)))
+                }
+                res => return res,
+            }
+        }
+    }
+
+    const fn next_part(mut self) -> Option<(Self, &'a str)> {
+        if self.finished || self.remaining == 0 {
+            return None;
+        }
+        if self.remaining == 1 {
+            return self.get_end();
+        }
+        if self.remaining != usize::MAX {
+            self.remaining -= 1;
+        }
+        if self.rev {
+            self.next_back()
+        } else {
+            self.next_front()
+        }
+    }
+
+    const fn next_front(mut self) -> Option<(Self, &'a str)> {
+        let found = if self.searching {
+            self.sep.find(self.s, self.pos)
+        } else {
+            None
+        };
+        let (match_start, match_end) = match found {
+            Some(found) => found,
+            None => return self.get_end(),
+        };
+        let piece_end = if self.inclusive {
+            match_end
+        } else {
+            match_start
+        };
+        let piece = self.piece(self.start, piece_end);
+        self.start = match_end;
+        self.pos = match_end;
+        if match_start == match_end {
+            // The empty pattern matches at every char boundary, skip to the next one.
+            match char_at(self.s, match_end) {
+                Some((_, next)) => self.pos = next,
+                None => self.searching = false,
+            }
+        }
+        Some((self, piece))
+    }
+
+    const fn next_back(mut self) -> Option<(Self, &'a str)> {
+        if !self.allow_trailing_empty {
+            // A pattern at the end of the String doesn't result in an empty String.
+            self.allow_trailing_empty = true;
+            match self.next_back() {
+                Some((split, piece)) if !piece.is_empty() => return Some((split, piece)),
+                Some((split, _)) => self = split,
+                None => return None,
+            }
+            if self.finished {
+                return None;
+            }
+        }
+        let found = if self.searching {
+            self.sep.rfind(self.s, self.pos)
+        } else {
+            None
+        };
+        let (match_start, match_end) = match found {
+            Some(found) => found,
+            None => return self.get_end(),
+        };
+        let piece = self.piece(match_end, self.end);
+        self.end = if self.inclusive {
+            match_end
+        } else {
+            match_start
+        };
+        self.pos = match_start;
+        if match_start == match_end {
+            if match_start == 0 {
+                self.searching = false;
+            } else {
+                self.pos = prev_char_boundary(self.s, match_start);
+            }
+        }
+        Some((self, piece))
+    }
+
+    const fn get_end(mut self) -> Option<(Self, &'a str)> {
+        self.finished = true;
+        if self.allow_trailing_empty || self.end > self.start {
+            let piece = self.piece(self.start, self.end);
+            Some((self, piece))
+        } else {
+            None
+        }
+    }
+
+    const fn piece(&self, start: usize, end: usize) -> &'a str {
+        match slice(self.s, start, end) {
+            Some(s) => s,
+            None => unreachable!(),
+        }
+    }
+}
+
+/// What separates the parts yielded by a [`SplitCursor`]
+#[derive(Clone, Copy, Debug)]
+enum Separator<'p> {
+    Pattern(Pattern<'p>),
+    Whitespace,
+    AsciiWhitespace,
+}
+
+impl Separator<'_> {
+    /// Returns the start and end of the first separator in `s[from..]`.
+    const fn find(&self, s: &str, from: usize) -> Option<(usize, usize)> {
+        if let Separator::Pattern(pat) = self {
+            return match find_bytes(s.as_bytes(), pat.as_bytes(), from) {
+                Some(idx) => Some((idx, idx + pat.len())),
+                None => None,
+            };
+        }
+        let mut idx = from;
+        while let Some((c, next)) = char_at(s, idx) {
+            if self.is_whitespace(c) {
+                return Some((idx, next));
+            }
+            idx = next;
+        }
+        None
+    }
+    /// Returns the start and end of the last separator in `s[..to]`.
+    const fn rfind(&self, s: &str, to: usize) -> Option<(usize, usize)> {
+        if let Separator::Pattern(pat) = self {
+            return match rfind_bytes(s.as_bytes(), pat.as_bytes(), to) {
+                Some(idx) => Some((idx, idx + pat.len())),
+                None => None,
+            };
+        }
+        let mut end = to;
+        while end > 0 {
+            let idx = prev_char_boundary(s, end);
+            match char_at(s, idx) {
+                Some((c, _)) if self.is_whitespace(c) => return Some((idx, end)),
+                _ => end = idx,
+            }
+        }
+        None
+    }
+    const fn is_whitespace(&self, c: char) -> bool {
+        match self {
+            Separator::Whitespace => c.is_whitespace(),
+            Separator::AsciiWhitespace => c.is_ascii_whitespace(),
+            Separator::Pattern(_) => false,
+        }
+    }
+}
+
+/// Removes the `\n` or `\r\n` ending a line, like [`str::lines`].
+const fn strip_line_ending(line: &str) -> &str {
+    let bytes = line.as_bytes();
+    match bytes {
+        [.., b'\r', b'\n'] => line.split_at(line.len() - 2).0,
+        [.., b'\n'] => line.split_at(line.len() - 1).0,
+        _ => line,
+    }
+}
+
+impl<'a> Iterator for SplitCursor<'a, '_> {
+    type Item = &'a str;
+    fn next(&mut self) -> Option<Self::Item> {
+        let (next, part) = SplitCursor::next(*self)?;
+        *self = next;
+        Some(part)
+    }
+}
+
+#[cfg(test)]
+mod split_test {
+    use super::*;
+
+    const S: KStr<20> = KStr::from_str("a::b::c€::");
+    const PARTS: KVec<KStr<4>, 4> = S.split_pattern(Pattern::str("::"));
+    const _: () = {
+        assert!(PARTS.len() == 4);
+        assert!(PARTS.get_unchecked(2).len() == 4);
+        assert!(PARTS.get_unchecked(3).is_empty());
+        assert!(
+            S.splitn::<2, 20>(2, Pattern::str("::"))
+                .get_unchecked(1)
+                .len()
+                == 9
+        );
+        assert!(
+            matches!(S.split_once(Pattern::char('€')), Some((a, b)) if a.len() == 7 && b.len() == 2)
+        );
+    };
+
+    fn to_vec<const L: usize, const NN: usize>(v: KVec<KStr<NN>, L>) -> Vec<String> {
+        v.buf[..v.len()]
+            .iter()
+            .map(|s| s.as_str().to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn parity_with_std() {
+        let hays = ["", ",", "a,b", ",a,,b,", "a,,", "αβ,γ", "ab"];
+        for hay in hays {
+            let k = KStr::<16>::from_str(hay);
+            for pat in [",", ",,", ""] {
+                let p = Pattern::str(pat);
+                assert_eq!(
+                    to_vec(k.split_pattern::<16, 16>(p)),
+                    hay.split(pat).collect::<Vec<_>>()
+                );
+                assert_eq!(
+                    to_vec(k.rsplit::<16, 16>(p)),
+                    hay.rsplit(pat).collect::<Vec<_>>()
+                );
+                assert_eq!(
+                    to_vec(k.split_terminator::<16, 16>(p)),
+                    hay.split_terminator(pat).collect::<Vec<_>>()
+                );
+                assert_eq!(
+                    to_vec(k.rsplit_terminator::<16, 16>(p)),
+                    hay.rsplit_terminator(pat).collect::<Vec<_>>()
+                );
+                for n in 0..4 {
+                    assert_eq!(
+                        to_vec(k.splitn::<16, 16>(n, p)),
+                        hay.splitn(n, pat).collect::<Vec<_>>()
+                    );
+                    assert_eq!(
+                        to_vec(k.rsplitn::<16, 16>(n, p)),
+                        hay.rsplitn(n, pat).collect::<Vec<_>>()
+                    );
+                }
+                assert_eq!(k.split_once(p), hay.split_once(pat));
+                assert_eq!(k.rsplit_once(p), hay.rsplit_once(pat));
+            }
+            assert_eq!(
+                to_vec(k.split_inclusive::<16, 16>(Pattern::char(','))),
+                hay.split_inclusive(',').collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn cursor() {
+        const S: KStr<32> = KStr::from_str("  let x =  1;\nlet y = 2;\n");
+        const WORDS: usize = {
+            let mut cursor = S.split_whitespace_cursor();
+            let mut count = 0;
+            while let Some((next, _)) = cursor.next() {
+                count += 1;
+                cursor = next;
+            }
+            count
+        };
+        assert_eq!(WORDS, 8);
+        assert_eq!(
+            S.lines_cursor().collect::<Vec<_>>(),
+            S.as_str().lines().collect::<Vec<_>>()
+        );
+
+        let mut cursor = SplitCursor::new("a=b=c", Pattern::char('=')).limit(2);
+        assert_eq!(cursor.remainder(), "a=b=c");
+        assert_eq!(Iterator::next(&mut cursor), Some("a"));
+        assert_eq!(cursor.remainder(), "b=c");
+        assert_eq!(cursor.collect::<Vec<_>>(), ["b=c"]);
+    }
+
+    const CORPUS: [&str; 16] = [
+        "",
+        " ",
+        "\n",
+        "\r\n",
+        "a",
+        " a",
+        "a ",
+        "\t a \t\n b\x0C",
+        "foo\r\nbar\n\nbaz\r",
+        "foo\nbar\n\r\n",
+        "\r\r\n\n",
+        "a\x0Bb",
+        "\u{a0}a\u{2003}b\u{3000}",
+        "héllo\twörld",
+        "one  two\n\nthree\r\n",
+        "\n\na\n",
+    ];
+
+    #[test]
+    fn whitespace_and_lines_parity_with_std() {
+        for s in CORPUS {
+            let k = KStr::<64>::from_str(s);
+            let std_lines = s.lines().collect::<Vec<_>>();
+            assert_eq!(to_vec(k.lines::<16, 64>()), std_lines, "{s:?}");
+            assert_eq!(k.lines_cursor().collect::<Vec<_>>(), std_lines, "{s:?}");
+            let std_lines_rev = s.lines().rev().collect::<Vec<_>>();
+            assert_eq!(
+                k.lines_cursor().reversed().collect::<Vec<_>>(),
+                std_lines_rev,
+                "{s:?}"
+            );
+
+            let std_words = s.split_whitespace().collect::<Vec<_>>();
+            assert_eq!(to_vec(k.split_whitespace::<16, 64>()), std_words, "{s:?}");
+            assert_eq!(k.split_whitespace_cursor().collect::<Vec<_>>(), std_words);
+            assert_eq!(
+                k.split_whitespace_cursor().reversed().collect::<Vec<_>>(),
+                s.split_whitespace().rev().collect::<Vec<_>>()
+            );
+
+            let std_ascii_words = s.split_ascii_whitespace().collect::<Vec<_>>();
+            assert_eq!(
+                to_vec(k.split_ascii_whitespace::<16, 64>()),
+                std_ascii_words,
+                "{s:?}"
+            );
+            assert_eq!(
+                k.split_ascii_whitespace_cursor().collect::<Vec<_>>(),
+                std_ascii_words
+            );
+        }
+    }
+
+    #[test]
+    fn split_on_byte() {
+        for hay in ["", ",", "a,b", ",a,,b,", "a,,", "αβ,γ"] {
+            let k = KStr::<16>::from_str(hay);
+            assert_eq!(
+                to_vec(k.split::<16, 16>(b',')),
+                hay.split_terminator(',').collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_many_parts() {
+        let _ = KStr::<8>::from_str("a,b,c").split::<2, 8>(b',');
+    }
+}
@@ -0,0 +1,166 @@
+use super::pattern::{bytes_eq, starts_with_bytes};
+use super::{char_at, prev_char_boundary, KStr, Pattern};
+
+// Trimming methods return a view into the String, use `KStr::<M>::from_str` on the result to
+// copy it into a String with a smaller buffer.
+impl<const N: usize> KStr<N> {
+    /// Returns a String slice with leading and trailing whitespace removed.
+    pub const fn trim(&self) -> &str {
+        trim_end(trim_start(self.as_str()))
+    }
+    /// Returns a String slice with leading whitespace removed.
+    pub const fn trim_start(&self) -> &str {
+        trim_start(self.as_str())
+    }
+    /// Returns a String slice with trailing whitespace removed.
+    pub const fn trim_end(&self) -> &str {
+        trim_end(self.as_str())
+    }
+    /// Returns a String slice with all leading and trailing matches of the pattern removed.
+    pub const fn trim_matches(&self, pat: Pattern) -> &str {
+        trim_end_matches(trim_start_matches(self.as_str(), pat), pat)
+    }
+    /// Returns a String slice with all leading matches of the pattern removed.
+    pub const fn trim_start_matches(&self, pat: Pattern) -> &str {
+        trim_start_matches(self.as_str(), pat)
+    }
+    /// Returns a String slice with all trailing matches of the pattern removed.
+    pub const fn trim_end_matches(&self, pat: Pattern) -> &str {
+        trim_end_matches(self.as_str(), pat)
+    }
+    /// Returns a String slice with the prefix removed, or None if the String doesn't start with it.
+    pub const fn strip_prefix(&self, pat: Pattern) -> Option<&str> {
+        strip_prefix(self.as_str(), pat)
+    }
+    /// Returns a String slice with the suffix removed, or None if the String doesn't end with it.
+    pub const fn strip_suffix(&self, pat: Pattern) -> Option<&str> {
+        strip_suffix(self.as_str(), pat)
+    }
+    /// Returns the String slice between the byte indices, or None if they are out of bounds
+    /// or not on char boundaries.
+    pub const fn slice(&self, start: usize, end: usize) -> Option<&str> {
+        slice(self.as_str(), start, end)
+    }
+    /// Returns a new String of capacity `M` holding the bytes between the indices.
+    /// # Panics: panic if the indices are out of bounds, not on char boundaries, or if the substring doesn't fit in `M`
+    #[must_use]
+    pub const fn substr<const M: usize>(&self, start: usize, end: usize) -> KStr<M> {
+        match self.slice(start, end) {
+            Some(s) => KStr::from_str(s),
+            None => panic!("Substring indices are out of bounds or not on a char boundary"),
+        }
+    }
+}
+
+pub(crate) const fn slice(s: &str, start: usize, end: usize) -> Option<&str> {
+    if start > end || end > s.len() || !s.is_char_boundary(start) || !s.is_char_boundary(end) {
+        return None;
+    }
+    Some(s.split_at(end).0.split_at(start).1)
+}
+
+/// Returns the index of the first byte from `idx` that is not a space or a tab.
+pub(crate) const fn skip_spaces(s: &str, mut idx: usize) -> usize {
+    let bytes = s.as_bytes();
+    while idx < bytes.len() && matches!(bytes[idx], b' ' | b'\t') {
+        idx += 1;
+    }
+    idx
+}
+
+pub(crate) const fn trim_start(mut s: &str) -> &str {
+    while let Some((c, next)) = char_at(s, 0) {
+        if !c.is_whitespace() {
+            break;
+        }
+        s = s.split_at(next).1;
+    }
+    s
+}
+
+pub(crate) const fn trim_end(mut s: &str) -> &str {
+    while !s.is_empty() {
+        let start = prev_char_boundary(s, s.len());
+        match char_at(s, start) {
+            Some((c, _)) if c.is_whitespace() => s = s.split_at(start).0,
+            _ => break,
+        }
+    }
+    s
+}
+
+pub(crate) const fn trim_start_matches<'a>(mut s: &'a str, pat: Pattern) -> &'a str {
+    if pat.is_empty() {
+        return s;
+    }
+    while let Some(rest) = strip_prefix(s, pat) {
+        s = rest;
+    }
+    s
+}
+
+pub(crate) const fn trim_end_matches<'a>(mut s: &'a str, pat: Pattern) -> &'a str {
+    if pat.is_empty() {
+        return s;
+    }
+    while let Some(rest) = strip_suffix(s, pat) {
+        s = rest;
+    }
+    s
+}
+
+pub(crate) const fn strip_prefix<'a>(s: &'a str, pat: Pattern) -> Option<&'a str> {
+    if starts_with_bytes(s.as_bytes(), pat.as_bytes()) {
+        Some(s.split_at(pat.len()).1)
+    } else {
+        None
+    }
+}
+
+pub(crate) const fn strip_suffix<'a>(s: &'a str, pat: Pattern) -> Option<&'a str> {
+    if s.len() < pat.len() || !s.is_char_boundary(s.len() - pat.len()) {
+        return None;
+    }
+    let (rest, suffix) = s.split_at(s.len() - pat.len());
+    if bytes_eq(suffix.as_bytes(), pat.as_bytes()) {
+        Some(rest)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod trim_test {
+    use super::*;
+
+    const S: KStr<30> = KStr::from_str("\t\u{3000} key = välue \n");
+    const TRIMMED: KStr<12> = KStr::from_str(S.trim());
+    const _: () = {
+        assert!(TRIMMED.len() == 12);
+        assert!(matches!(
+            TRIMMED.strip_prefix(Pattern::str("key")),
+            Some(s) if s.len() == 9
+        ));
+        assert!(TRIMMED.strip_suffix(Pattern::str("key")).is_none());
+        assert!(TRIMMED.substr::<4>(9, 12).len() == 3);
+        assert!(TRIMMED.slice(8, 9).is_none());
+    };
+
+    #[test]
+    fn parity_with_std() {
+        for s in ["", "  ", "xxaxx", " a b ", "\u{85}a\u{a0}", "ababcab"] {
+            let k = KStr::<20>::from_str(s);
+            assert_eq!(k.trim(), s.trim());
+            assert_eq!(k.trim_start(), s.trim_start());
+            assert_eq!(k.trim_end(), s.trim_end());
+            assert_eq!(k.trim_matches('x'.into()), s.trim_matches('x'));
+            assert_eq!(
+                k.trim_start_matches("ab".into()),
+                s.trim_start_matches("ab")
+            );
+            assert_eq!(k.trim_end_matches("ab".into()), s.trim_end_matches("ab"));
+            assert_eq!(k.strip_prefix("xx".into()), s.strip_prefix("xx"));
+            assert_eq!(k.strip_suffix("ab".into()), s.strip_suffix("ab"));
+        }
+    }
+}
@@ -45,6 +45,31 @@ impl<T, const N: usize> KVec<T, N> {
         }
         Some(&self.buf[self.cursor - 1])
     }
+    /// Returns a slice of the Vector elements.
+    pub const fn as_slice(&self) -> &[T] {
+        self.buf.split_at(self.cursor).0
+    }
+}
+
+impl<T: Copy, const N: usize> KVec<T, N> {
+    /// Returns an empty Vector, its backing buffer is filled with `fill`.
+    pub const fn new(fill: T) -> Self {
+        Self {
+            buf: [fill; N],
+            cursor: 0,
+        }
+    }
+    /// Returns a new Vector with a given elem appended to it.
+    /// # Panics: panic if the Vector is full
+    #[must_use]
+    pub const fn push(mut self, elem: T) -> Self {
+        if self.cursor == N {
+            panic!("KVec capacity exceeded");
+        }
+        self.buf[self.cursor] = elem;
+        self.cursor += 1;
+        self
+    }
 }
 
 impl<T: std::fmt::Debug, const N: usize> std::fmt::Debug for KVec<T, N> {
@@ -17,6 +17,21 @@
 //!    };
 //! ```
 
+/// This module contains const color parsing and terminal palette downsampling
+pub mod color;
+/// This module contains a const CSV reader with typed column extraction
+pub mod csv;
+/// This module contains const hex and base64 encoders and decoders
+pub mod encoding;
+mod error;
+/// This module contains const glob pattern matching
+pub mod glob;
+/// This module contains const FNV-1a, CRC-32, MurmurHash3 and XXH3 hash functions
+pub mod hash;
+/// This module contains a const INI config parser with TOML values
+pub mod ini;
+/// This module contains a const JSON parser storing the document in fixed capacity arenas
+pub mod json;
 /// This module contains a Map like struct that can be used in const context
 pub mod kmap;
 /// This module contains a Set like struct that can be used in const context
@@ -25,3 +40,17 @@ pub mod kset;
 pub mod kstr;
 /// This module contains a String like struct that can be used in const context
 pub mod kvec;
+/// This module contains const IP address and CIDR block parsers
+pub mod net;
+/// This module contains a regex subset compiled in const context
+pub mod regex;
+/// This module contains a const template parser for styled terminal output
+pub mod template;
+/// This module contains const RFC 3339 timestamp and duration parsers
+pub mod time;
+/// This module contains a const URL parser and percent-encoding helpers
+pub mod url;
+/// This module contains const UUID parsing and formatting
+pub mod uuid;
+/// This module contains a const semantic version type
+pub mod version;
@@ -0,0 +1,512 @@
+//! Addresses are parsed like their `FromStr` implementations in the standard library: IPv4 is
+//! four decimal octets without leading zeros, IPv6 is eight groups of 1 to 4 hex digits where
+//! one run of groups can be compressed to `::` and the last two groups can be written as IPv4.
+//!
+//! CIDR blocks are an address followed by `/` and a prefix length, and the bits of the address
+//! past the prefix must be zero, so a typo like `10.0.0.1/8` is an error.
+//!
+//! ```rust
+//! use konster::net::{parse_ipv4, parse_ipv6, Ipv4Cidr};
+//! use std::net::{Ipv4Addr, Ipv6Addr};
+//!
+//! const ADDR: Ipv4Addr = parse_ipv4("10.0.0.1");
+//! const PRIVATE: Ipv4Cidr = Ipv4Cidr::parse("10.0.0.0/8");
+//! const _: () = assert!(PRIVATE.contains(ADDR));
+//! const LOOPBACK: Ipv6Addr = parse_ipv6("::1");
+//! assert_eq!(LOOPBACK, Ipv6Addr::LOCALHOST);
+//! assert!(!PRIVATE.contains(Ipv4Addr::new(11, 0, 0, 1)));
+//! ```
+use crate::error::impl_error;
+use crate::kstr::{hex_digit, parse_u64, split_once, Pattern, SplitCursor};
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+/// Error returned when parsing a malformed address or CIDR block
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NetError {
+    /// What is wrong with the address
+    pub kind: NetErrorKind,
+    /// Byte index in the input where the error was found
+    pub offset: usize,
+}
+
+/// The different ways an address or CIDR block can be malformed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NetErrorKind {
+    /// An IPv4 part that is not a number from 0 to 255 without leading zeros
+    InvalidOctet,
+    /// An IPv6 part that is not 1 to 4 hex digits
+    InvalidGroup,
+    /// An address with too many or too few parts
+    WrongPartCount,
+    /// An IPv6 address with more than one `::`
+    MultipleCompressions,
+    /// A missing `/`, or a prefix length that is not a number or is too long for the address
+    InvalidPrefix,
+    /// An address with bits set past the prefix length
+    HostBitsSet,
+}
+
+impl NetErrorKind {
+    /// Returns a description of the error.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            NetErrorKind::InvalidOctet => "invalid octet",
+            NetErrorKind::InvalidGroup => "invalid group",
+            NetErrorKind::WrongPartCount => "wrong number of parts",
+            NetErrorKind::MultipleCompressions => "more than one '::'",
+            NetErrorKind::InvalidPrefix => "invalid prefix length",
+            NetErrorKind::HostBitsSet => "host bits set",
+        }
+    }
+}
+
+impl NetError {
+    const fn new(kind: NetErrorKind, offset: usize) -> Self {
+        Self { kind, offset }
+    }
+}
+impl_error!(NetError, " at byte " offset);
+
+/// Parses an IPv4 address.
+/// # Panics: panic if the address is malformed
+pub const fn parse_ipv4(s: &str) -> Ipv4Addr {
+    match try_parse_ipv4(s) {
+        Ok(addr) => addr,
+        Err(err) => err.panic(),
+    }
+}
+
+/// Parses an IPv4 address, returning an error if it is malformed.
+pub const fn try_parse_ipv4(s: &str) -> Result<Ipv4Addr, NetError> {
+    match ipv4_bits(s, 0) {
+        Ok(bits) => Ok(Ipv4Addr::from_bits(bits)),
+        Err(err) => Err(err),
+    }
+}
+
+/// Parses an IPv6 address.
+/// # Panics: panic if the address is malformed
+pub const fn parse_ipv6(s: &str) -> Ipv6Addr {
+    match try_parse_ipv6(s) {
+        Ok(addr) => addr,
+        Err(err) => err.panic(),
+    }
+}
+
+/// Parses an IPv6 address, returning an error if it is malformed.
+pub const fn try_parse_ipv6(s: &str) -> Result<Ipv6Addr, NetError> {
+    let (head, tail, compressed) = match split_once(s, Pattern::str("::")) {
+        Some((head, tail)) => (head, tail, true),
+        None => (s, "", false),
+    };
+    let tail_start = head.len() + 2;
+    if let Some((extra, _)) = split_once(tail, Pattern::str("::")) {
+        return Err(NetError::new(
+            NetErrorKind::MultipleCompressions,
+            tail_start + extra.len(),
+        ));
+    }
+    let (head, head_len) = match groups(head, 0, !compressed) {
+        Ok(found) => found,
+        Err(err) => return Err(err),
+    };
+    let (tail, tail_len) = match groups(tail, tail_start, true) {
+        Ok(found) => found,
+        Err(err) => return Err(err),
+    };
+    if !compressed && head_len < 8 {
+        return Err(NetError::new(NetErrorKind::WrongPartCount, s.len()));
+    }
+    // `::` stands for at least one group
+    if compressed && head_len + tail_len > 7 {
+        return Err(NetError::new(NetErrorKind::WrongPartCount, tail_start - 2));
+    }
+    let mut bits: u128 = 0;
+    let mut idx = 0;
+    while idx < head_len {
+        bits |= (head[idx] as u128) << (112 - 16 * idx);
+        idx += 1;
+    }
+    idx = 0;
+    while idx < tail_len {
+        bits |= (tail[idx] as u128) << (16 * (tail_len - 1 - idx));
+        idx += 1;
+    }
+    Ok(Ipv6Addr::from_bits(bits))
+}
+
+/// An IPv4 network, like `10.0.0.0/8`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Ipv4Cidr {
+    network: Ipv4Addr,
+    prefix_len: u8,
+}
+
+impl Ipv4Cidr {
+    /// Parses a CIDR block.
+    /// # Panics: panic if the block is malformed
+    pub const fn parse(s: &str) -> Self {
+        match Self::try_parse(s) {
+            Ok(cidr) => cidr,
+            Err(err) => err.panic(),
+        }
+    }
+
+    /// Parses a CIDR block, returning an error if it is malformed.
+    pub const fn try_parse(s: &str) -> Result<Self, NetError> {
+        let (addr, prefix_len) = match prefix(s, 32) {
+            Ok(found) => found,
+            Err(err) => return Err(err),
+        };
+        let bits = match ipv4_bits(addr, 0) {
+            Ok(bits) => bits,
+            Err(err) => return Err(err),
+        };
+        let cidr = Self {
+            network: Ipv4Addr::from_bits(bits),
+            prefix_len,
+        };
+        if bits & !cidr.netmask().to_bits() != 0 {
+            return Err(NetError::new(NetErrorKind::HostBitsSet, 0));
+        }
+        Ok(cidr)
+    }
+
+    /// Returns the first address of the network
+    pub const fn network(&self) -> Ipv4Addr {
+        self.network
+    }
+
+    /// Returns the number of leading bits shared by the addresses of the network
+    pub const fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Returns the mask of the prefix bits, like `255.0.0.0` for `/8`
+    pub const fn netmask(&self) -> Ipv4Addr {
+        Ipv4Addr::from_bits(match u32::MAX.checked_shl(32 - self.prefix_len as u32) {
+            Some(mask) => mask,
+            None => 0,
+        })
+    }
+
+    /// Returns whether the address is part of the network
+    pub const fn contains(&self, addr: Ipv4Addr) -> bool {
+        addr.to_bits() & self.netmask().to_bits() == self.network.to_bits()
+    }
+}
+
+impl std::fmt::Display for Ipv4Cidr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.network, self.prefix_len)
+    }
+}
+
+/// An IPv6 network, like `fe80::/10`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Ipv6Cidr {
+    network: Ipv6Addr,
+    prefix_len: u8,
+}
+
+impl Ipv6Cidr {
+    /// Parses a CIDR block.
+    /// # Panics: panic if the block is malformed
+    pub const fn parse(s: &str) -> Self {
+        match Self::try_parse(s) {
+            Ok(cidr) => cidr,
+            Err(err) => err.panic(),
+        }
+    }
+
+    /// Parses a CIDR block, returning an error if it is malformed.
+    pub const fn try_parse(s: &str) -> Result<Self, NetError> {
+        let (addr, prefix_len) = match prefix(s, 128) {
+            Ok(found) => found,
+            Err(err) => return Err(err),
+        };
+        let network = match try_parse_ipv6(addr) {
+            Ok(network) => network,
+            Err(err) => return Err(err),
+        };
+        let cidr = Self {
+            network,
+            prefix_len,
+        };
+        if network.to_bits() & !cidr.netmask().to_bits() != 0 {
+            return Err(NetError::new(NetErrorKind::HostBitsSet, 0));
+        }
+        Ok(cidr)
+    }
+
+    /// Returns the first address of the network
+    pub const fn network(&self) -> Ipv6Addr {
+        self.network
+    }
+
+    /// Returns the number of leading bits shared by the addresses of the network
+    pub const fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Returns the mask of the prefix bits, like `ffc0::` for `/10`
+    pub const fn netmask(&self) -> Ipv6Addr {
+        Ipv6Addr::from_bits(match u128::MAX.checked_shl(128 - self.prefix_len as u32) {
+            Some(mask) => mask,
+            None => 0,
+        })
+    }
+
+    /// Returns whether the address is part of the network
+    pub const fn contains(&self, addr: Ipv6Addr) -> bool {
+        addr.to_bits() & self.netmask().to_bits() == self.network.to_bits()
+    }
+}
+
+impl std::fmt::Display for Ipv6Cidr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.network, self.prefix_len)
+    }
+}
+
+/// Returns whether the part is only ASCII digits, `str::parse` also accepts a sign.
+const fn all_digits(part: &str) -> bool {
+    let bytes = part.as_bytes();
+    let mut idx = 0;
+    while idx < bytes.len() {
+        if !bytes[idx].is_ascii_digit() {
+            return false;
+        }
+        idx += 1;
+    }
+    !bytes.is_empty()
+}
+
+/// Splits the address from the prefix length, which must be at most `max`.
+const fn prefix(s: &str, max: u64) -> Result<(&str, u8), NetError> {
+    let (addr, len) = match split_once(s, Pattern::byte(b'/')) {
+        Some(found) => found,
+        None => return Err(NetError::new(NetErrorKind::InvalidPrefix, s.len())),
+    };
+    let invalid = NetError::new(NetErrorKind::InvalidPrefix, addr.len() + 1);
+    if !all_digits(len) || (len.len() > 1 && len.as_bytes()[0] == b'0') {
+        return Err(invalid);
+    }
+    match parse_u64(len) {
+        Ok(len) if len <= max => Ok((addr, len as u8)),
+        _ => Err(invalid),
+    }
+}
+
+/// Parses the dotted octets of an IPv4 address starting at byte `base` of the input.
+const fn ipv4_bits(s: &str, base: usize) -> Result<u32, NetError> {
+    let mut cursor = SplitCursor::new(s, Pattern::byte(b'.'));
+    let mut bits: u32 = 0;
+    let mut count = 0;
+    let mut offset = base;
+    while let Some((next, part)) = cursor.next() {
+        if count == 4 {
+            return Err(NetError::new(NetErrorKind::WrongPartCount, offset));
+        }
+        let invalid = NetError::new(NetErrorKind::InvalidOctet, offset);
+        if !all_digits(part) || (part.len() > 1 && part.as_bytes()[0] == b'0') {
+            return Err(invalid);
+        }
+        bits = bits << 8
+            | match parse_u64(part) {
+                Ok(octet) if octet <= 255 => octet as u32,
+                _ => return Err(invalid),
+            };
+        count += 1;
+        offset += part.len() + 1;
+        cursor = next;
+    }
+    if count < 4 {
+        return Err(NetError::new(NetErrorKind::WrongPartCount, base + s.len()));
+    }
+    Ok(bits)
+}
+
+/// Parses the `:` separated groups of an IPv6 address starting at byte `base` of the input,
+/// the last group can be an IPv4 address if `ipv4_last`.
+const fn groups(s: &str, base: usize, ipv4_last: bool) -> Result<([u16; 8], usize), NetError> {
+    let mut groups = [0; 8];
+    let mut count = 0;
+    if s.is_empty() {
+        return Ok((groups, count));
+    }
+    let mut cursor = SplitCursor::new(s, Pattern::byte(b':'));
+    let mut offset = base;
+    while let Some((next, part)) = cursor.next() {
+        let is_last = offset + part.len() == base + s.len();
+        if is_last && ipv4_last && count <= 6 && split_once(part, Pattern::byte(b'.')).is_some() {
+            let bits = match ipv4_bits(part, offset) {
+                Ok(bits) => bits,
+                Err(err) => return Err(err),
+            };
+            groups[count] = (bits >> 16) as u16;
+            groups[count + 1] = bits as u16;
+            return Ok((groups, count + 2));
+        }
+        if count == 8 {
+            return Err(NetError::new(NetErrorKind::WrongPartCount, offset));
+        }
+        groups[count] = match group(part) {
+            Some(group) => group,
+            None => return Err(NetError::new(NetErrorKind::InvalidGroup, offset)),
+        };
+        count += 1;
+        offset += part.len() + 1;
+        cursor = next;
+    }
+    Ok((groups, count))
+}
+
+const fn group(part: &str) -> Option<u16> {
+    let bytes = part.as_bytes();
+    if bytes.is_empty() || bytes.len() > 4 {
+        return None;
+    }
+    let mut group: u16 = 0;
+    let mut idx = 0;
+    while idx < bytes.len() {
+        let digit = match hex_digit(bytes[idx]) {
+            Some(digit) => digit,
+            None => return None,
+        };
+        group = group << 4 | digit as u16;
+        idx += 1;
+    }
+    Some(group)
+}
+
+#[cfg(test)]
+mod net_test {
+    use super::*;
+
+    const NET: Ipv6Cidr = Ipv6Cidr::parse("2001:db8::/32");
+    const _: () = {
+        assert!(NET.contains(parse_ipv6("2001:db8:ffff::1")));
+        assert!(!NET.contains(parse_ipv6("2001:db9::")));
+        assert!(parse_ipv4("192.168.1.20").octets()[3] == 20);
+    };
+
+    #[test]
+    fn parity_with_std() {
+        for s in [
+            "0.0.0.0",
+            "255.255.255.255",
+            "1.2.3.4",
+            "256.0.0.1",
+            "01.2.3.4",
+            "1.2.3",
+            "1.2.3.4.5",
+            "1..3.4",
+            "+1.2.3.4",
+            "1.2.3.4 ",
+            "",
+            "::",
+            "::1",
+            "1::",
+            "1:2:3:4:5:6:7:8",
+            "1:2:3:4:5:6:7:8:9",
+            "1:2:3:4:5:6:7",
+            "1:2:3:4:5:6:7::",
+            "::2:3:4:5:6:7:8",
+            "1:2:3:4:5:6:7:8::",
+            "::1:2:3:4:5:6:7:8",
+            "1::2::3",
+            ":::",
+            ":1::",
+            "1:",
+            ":1",
+            "12345::",
+            "fFfF::AbCd",
+            "g::",
+            "::ffff:192.168.0.1",
+            "::192.168.0.1",
+            "1:2:3:4:5:6:1.2.3.4",
+            "1:2:3:4:5:6:7:1.2.3.4",
+            "1:2:3:4:5::1.2.3.4",
+            "1.2.3.4::",
+            "::1.2.3.4:1",
+            "::1.2.3",
+            "::01.2.3.4",
+            "0000:0000::0000",
+            "1::2:3:4:5:6:7",
+        ] {
+            assert_eq!(try_parse_ipv4(s).ok(), s.parse().ok(), "{s:?}");
+            assert_eq!(try_parse_ipv6(s).ok(), s.parse().ok(), "{s:?}");
+        }
+    }
+
+    #[test]
+    fn errors() {
+        for (s, kind, offset) in [
+            ("1.2.300.4", NetErrorKind::InvalidOctet, 4),
+            ("1.2.3", NetErrorKind::WrongPartCount, 5),
+            ("1.2.3.4.5", NetErrorKind::WrongPartCount, 8),
+        ] {
+            assert_eq!(try_parse_ipv4(s), Err(NetError::new(kind, offset)), "{s}");
+        }
+        for (s, kind, offset) in [
+            ("1::2::3", NetErrorKind::MultipleCompressions, 4),
+            ("1:2:3", NetErrorKind::WrongPartCount, 5),
+            ("1:2:3:4:5:6:7:8::", NetErrorKind::WrongPartCount, 15),
+            ("1::x", NetErrorKind::InvalidGroup, 3),
+            ("::1.2.3.256", NetErrorKind::InvalidOctet, 8),
+        ] {
+            assert_eq!(try_parse_ipv6(s), Err(NetError::new(kind, offset)), "{s}");
+        }
+        for (s, kind, offset) in [
+            ("10.0.0.0", NetErrorKind::InvalidPrefix, 8),
+            ("10.0.0.0/33", NetErrorKind::InvalidPrefix, 9),
+            ("10.0.0.0/08", NetErrorKind::InvalidPrefix, 9),
+            ("10.0.0.0/", NetErrorKind::InvalidPrefix, 9),
+            ("10.0.0.1/8", NetErrorKind::HostBitsSet, 0),
+            ("10.0.0/8", NetErrorKind::WrongPartCount, 6),
+        ] {
+            assert_eq!(
+                Ipv4Cidr::try_parse(s),
+                Err(NetError::new(kind, offset)),
+                "{s}"
+            );
+        }
+        assert_eq!(
+            NetError::new(NetErrorKind::HostBitsSet, 0).to_string(),
+            "host bits set at byte 0"
+        );
+    }
+
+    #[test]
+    fn cidr() {
+        let all = Ipv4Cidr::parse("0.0.0.0/0");
+        assert_eq!(all.netmask(), Ipv4Addr::UNSPECIFIED);
+        assert!(all.contains(Ipv4Addr::BROADCAST));
+        let host = Ipv4Cidr::parse("192.168.1.7/32");
+        assert!(host.contains(Ipv4Addr::new(192, 168, 1, 7)));
+        assert!(!host.contains(Ipv4Addr::new(192, 168, 1, 8)));
+        let net = Ipv4Cidr::parse("172.16.0.0/12");
+        assert_eq!(net.netmask(), Ipv4Addr::new(255, 240, 0, 0));
+        assert!(net.contains(Ipv4Addr::new(172, 31, 255, 255)));
+        assert!(!net.contains(Ipv4Addr::new(172, 32, 0, 0)));
+        assert_eq!(net.to_string(), "172.16.0.0/12");
+        assert_eq!(net.prefix_len(), 12);
+        let link = Ipv6Cidr::parse("fe80::/10");
+        assert_eq!(link.netmask(), parse_ipv6("ffc0::"));
+        assert!(link.contains(parse_ipv6("febf::1")));
+        assert!(!link.contains(parse_ipv6("fec0::")));
+        assert!(Ipv6Cidr::parse("::/0").contains(Ipv6Addr::LOCALHOST));
+        assert!(Ipv6Cidr::parse("::1/128").contains(Ipv6Addr::LOCALHOST));
+        assert_eq!(
+            Ipv6Cidr::try_parse("::1/127"),
+            Err(NetError::new(NetErrorKind::HostBitsSet, 0))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn malformed() {
+        let _ = parse_ipv4("10.0.0");
+    }
+}
@@ -0,0 +1,596 @@
+//! The supported syntax is literals, `.` (any char but `\n`), classes like `[a-z_]` or `[^0-9]`,
+//! the ASCII classes `\d`, `\w`, `\s` and their negations `\D`, `\W`, `\S`, the greedy `*`, `+`, `?`
+//! and their lazy `*?`, `+?`, `??` versions, alternation `|`, groups `(...)`, and the `^`/`$` anchors
+//! which match at the start and the end of the text. Other metachars can be escaped with `\`.
+//!
+//! Matches are leftmost-first like the `regex` crate: among the matches starting at the leftmost
+//! position, the one preferred by the alternations and repetitions wins.
+//!
+//! ```rust
+//! use konster::regex::KRegex;
+//!
+//! const SEMVER: KRegex<64> = KRegex::parse(r"\d+\.\d+\.\d+(-[a-z]+)?");
+//! const _: () = assert!(SEMVER.is_match("v1.2.3"));
+//! assert_eq!(SEMVER.find("konster 0.4.1-beta!"), Some((8, 18)));
+//! ```
+use crate::error::impl_error;
+use crate::kstr::char_at;
+use crate::kvec::KVec;
+
+/// Error returned when compiling a malformed regex
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RegexError {
+    /// What is wrong with the pattern
+    pub kind: RegexErrorKind,
+    /// Byte index in the pattern where the error was found
+    pub offset: usize,
+}
+
+/// The different ways a regex can be malformed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RegexErrorKind {
+    /// A `(` without its closing `)`
+    UnclosedGroup,
+    /// A `)` without its opening `(`
+    UnmatchedParen,
+    /// A `*`, `+` or `?` that does not follow anything
+    NothingToRepeat,
+    /// A `[` without its closing `]`
+    UnterminatedClass,
+    /// A range like `[z-a]` whose end is before its start, or whose bound is a class
+    InvalidRange,
+    /// An escape that is not supported
+    InvalidEscape,
+    /// A `\` at the end of the pattern
+    TrailingEscape,
+}
+
+impl RegexErrorKind {
+    /// Returns a short description of the error
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            RegexErrorKind::UnclosedGroup => "unclosed group",
+            RegexErrorKind::UnmatchedParen => "unmatched parenthesis",
+            RegexErrorKind::NothingToRepeat => "nothing to repeat",
+            RegexErrorKind::UnterminatedClass => "unterminated class",
+            RegexErrorKind::InvalidRange => "invalid range",
+            RegexErrorKind::InvalidEscape => "invalid escape",
+            RegexErrorKind::TrailingEscape => "trailing escape",
+        }
+    }
+}
+
+impl RegexError {
+    const fn new(kind: RegexErrorKind, offset: usize) -> Self {
+        Self { kind, offset }
+    }
+}
+impl_error!(RegexError, " at byte " offset);
+
+/// Instructions of the compiled NFA, jump targets are indices in the program
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Inst {
+    Char(char),
+    /// Any char but `\n`
+    Any,
+    /// Matches a char in `ranges[start..end]`, or not in it when negated
+    Class {
+        start: usize,
+        end: usize,
+        negated: bool,
+    },
+    /// Forks the thread, the first target has priority
+    Split(usize, usize),
+    Jmp(usize),
+    AssertStart,
+    AssertEnd,
+    Match,
+}
+
+/// A regex compiled into a program of at most `N` instructions and `N` class ranges
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KRegex<const N: usize> {
+    prog: KVec<Inst, N>,
+    ranges: KVec<(char, char), N>,
+}
+
+impl<const N: usize> KRegex<N> {
+    /// Compiles the pattern.
+    /// # Panics: panic if the pattern is malformed or the program doesn't fit in `N`
+    pub const fn parse(pattern: &str) -> Self {
+        match Self::try_parse(pattern) {
+            Ok(regex) => regex,
+            Err(err) => err.panic(),
+        }
+    }
+    /// Compiles the pattern, or returns where it is malformed.
+    /// # Panics: panic if the program doesn't fit in `N`
+    pub const fn try_parse(pattern: &str) -> Result<Self, RegexError> {
+        let mut compiler = Compiler {
+            pattern,
+            pos: 0,
+            prog: KVec::new(Inst::Match),
+            ranges: KVec::new(('\0', '\0')),
+        };
+        if let Err(err) = compiler.alternation() {
+            return Err(err);
+        }
+        if compiler.pos < pattern.len() {
+            return Err(RegexError::new(
+                RegexErrorKind::UnmatchedParen,
+                compiler.pos,
+            ));
+        }
+        compiler.emit(Inst::Match);
+        Ok(Self {
+            prog: compiler.prog,
+            ranges: compiler.ranges,
+        })
+    }
+    /// Returns true if the regex matches somewhere in the text
+    pub const fn is_match(&self, text: &str) -> bool {
+        self.find(text).is_some()
+    }
+    /// Returns the byte range of the leftmost-first match in the text
+    pub const fn find(&self, text: &str) -> Option<(usize, usize)> {
+        let prog = self.prog.as_slice();
+        let mut current = Threads::<N>::new();
+        let mut matched = None;
+        let mut pos = 0;
+        loop {
+            if matched.is_none() {
+                // A match starting here has a lower priority than the ones already running
+                current.add(prog, 0, pos, pos, text.len());
+            }
+            let (c, next_pos) = match char_at(text, pos) {
+                Some((c, next)) => (Some(c), next),
+                None => (None, pos),
+            };
+            let mut next = Threads::<N>::new();
+            let mut idx = 0;
+            while idx < current.len {
+                let (pc, start) = (current.pcs[idx], current.starts[idx]);
+                let step = match (prog[pc], c) {
+                    (Inst::Match, _) => {
+                        // Threads after this one have a lower priority
+                        matched = Some((start, pos));
+                        break;
+                    }
+                    (Inst::Char(expected), Some(c)) => c == expected,
+                    (Inst::Any, Some(c)) => c != '\n',
+                    (
+                        Inst::Class {
+                            start,
+                            end,
+                            negated,
+                        },
+                        Some(c),
+                    ) => self.class_contains(start, end, c) != negated,
+                    _ => false,
+                };
+                if step {
+                    next.add(prog, pc + 1, start, next_pos, text.len());
+                }
+                idx += 1;
+            }
+            if c.is_none() || (next.len == 0 && matched.is_some()) {
+                return matched;
+            }
+            current = next;
+            pos = next_pos;
+        }
+    }
+    const fn class_contains(&self, start: usize, end: usize, c: char) -> bool {
+        let mut idx = start;
+        while idx < end {
+            let (lo, hi) = self.ranges.buf[idx];
+            if lo <= c && c <= hi {
+                return true;
+            }
+            idx += 1;
+        }
+        false
+    }
+}
+
+/// Threads of the Pike VM waiting on the same char, in priority order
+struct Threads<const N: usize> {
+    pcs: [usize; N],
+    starts: [usize; N],
+    len: usize,
+    seen: [bool; N],
+}
+
+impl<const N: usize> Threads<N> {
+    const fn new() -> Self {
+        Self {
+            pcs: [0; N],
+            starts: [0; N],
+            len: 0,
+            seen: [false; N],
+        }
+    }
+    /// Follows the jumps and assertions from `pc`, keeping the instructions that consume chars.
+    const fn add(&mut self, prog: &[Inst], pc: usize, start: usize, pos: usize, text_len: usize) {
+        if self.seen[pc] {
+            return;
+        }
+        self.seen[pc] = true;
+        match prog[pc] {
+            Inst::Jmp(target) => self.add(prog, target, start, pos, text_len),
+            Inst::Split(first, second) => {
+                self.add(prog, first, start, pos, text_len);
+                self.add(prog, second, start, pos, text_len);
+            }
+            Inst::AssertStart if pos == 0 => self.add(prog, pc + 1, start, pos, text_len),
+            Inst::AssertEnd if pos == text_len => self.add(prog, pc + 1, start, pos, text_len),
+            Inst::AssertStart | Inst::AssertEnd => {}
+            _ => {
+                self.pcs[self.len] = pc;
+                self.starts[self.len] = start;
+                self.len += 1;
+            }
+        }
+    }
+}
+
+const DIGIT: &[(char, char)] = &[('0', '9')];
+const WORD: &[(char, char)] = &[('0', '9'), ('A', 'Z'), ('_', '_'), ('a', 'z')];
+const SPACE: &[(char, char)] = &[('\t', '\r'), (' ', ' ')];
+
+enum Escape {
+    Char(char),
+    Class(&'static [(char, char)], bool),
+}
+
+/// Recursive descent parser emitting the program as it goes
+struct Compiler<'a, const N: usize> {
+    pattern: &'a str,
+    pos: usize,
+    prog: KVec<Inst, N>,
+    ranges: KVec<(char, char), N>,
+}
+
+impl<const N: usize> Compiler<'_, N> {
+    const fn peek(&self) -> Option<char> {
+        match char_at(self.pattern, self.pos) {
+            Some((c, _)) => Some(c),
+            None => None,
+        }
+    }
+    const fn emit(&mut self, inst: Inst) -> usize {
+        self.prog = self.prog.push(inst);
+        self.prog.len() - 1
+    }
+    /// Inserts an instruction before the code emitted since `at`, the moved code keeps its targets.
+    const fn insert(&mut self, at: usize, inst: Inst) {
+        self.prog = self.prog.push(inst);
+        let mut idx = self.prog.len() - 1;
+        while idx > at {
+            self.prog.buf[idx] = match self.prog.buf[idx - 1] {
+                Inst::Split(first, second) => Inst::Split(first + 1, second + 1),
+                Inst::Jmp(target) => Inst::Jmp(target + 1),
+                moved => moved,
+            };
+            idx -= 1;
+        }
+        self.prog.buf[at] = inst;
+    }
+    const fn alternation(&mut self) -> Result<(), RegexError> {
+        let start = self.prog.len();
+        if let Err(err) = self.concatenation() {
+            return Err(err);
+        }
+        while let Some('|') = self.peek() {
+            self.pos += 1;
+            self.insert(start, Inst::Match);
+            let jmp = self.emit(Inst::Match);
+            self.prog.buf[start] = Inst::Split(start + 1, self.prog.len());
+            if let Err(err) = self.concatenation() {
+                return Err(err);
+            }
+            self.prog.buf[jmp] = Inst::Jmp(self.prog.len());
+        }
+        Ok(())
+    }
+    const fn concatenation(&mut self) -> Result<(), RegexError> {
+        loop {
+            match self.peek() {
+                None | Some('|' | ')') => return Ok(()),
+                Some(_) => {}
+            }
+            let start = self.prog.len();
+            if let Err(err) = self.atom() {
+                return Err(err);
+            }
+            self.repetition(start);
+        }
+    }
+    const fn repetition(&mut self, start: usize) {
+        while let Some(op @ ('*' | '+' | '?')) = self.peek() {
+            self.pos += 1;
+            let lazy = matches!(self.peek(), Some('?'));
+            if lazy {
+                self.pos += 1;
+            }
+            match op {
+                '*' if self.reaches(start, self.prog.len(), &mut [false; N]) => {
+                    // An empty iteration would loop back to the split already followed and be dropped,
+                    // `e*` is compiled as `(e+)?` so the iteration that matches nothing is preferred
+                    self.emit(split(start, self.prog.len() + 1, lazy));
+                    self.insert(start, Inst::Match);
+                    self.prog.buf[start] = split(start + 1, self.prog.len(), lazy);
+                }
+                '*' => {
+                    self.insert(start, Inst::Match);
+                    self.emit(Inst::Jmp(start));
+                    self.prog.buf[start] = split(start + 1, self.prog.len(), lazy);
+                }
+                '+' => {
+                    let out = self.prog.len() + 1;
+                    self.emit(split(start, out, lazy));
+                }
+                _ => {
+                    self.insert(start, Inst::Match);
+                    self.prog.buf[start] = split(start + 1, self.prog.len(), lazy);
+                }
+            }
+        }
+    }
+    /// Returns true if the code from `pc` can reach `end` without consuming a char.
+    const fn reaches(&self, pc: usize, end: usize, seen: &mut [bool; N]) -> bool {
+        if pc == end {
+            return true;
+        }
+        if seen[pc] {
+            return false;
+        }
+        seen[pc] = true;
+        match self.prog.buf[pc] {
+            Inst::Split(first, second) => {
+                self.reaches(first, end, seen) || self.reaches(second, end, seen)
+            }
+            Inst::Jmp(target) => self.reaches(target, end, seen),
+            Inst::AssertStart | Inst::AssertEnd => self.reaches(pc + 1, end, seen),
+            _ => false,
+        }
+    }
+    const fn atom(&mut self) -> Result<(), RegexError> {
+        let offset = self.pos;
+        let c = match char_at(self.pattern, self.pos) {
+            Some((c, next)) => {
+                self.pos = next;
+                c
+            }
+            None => unreachable!(),
+        };
+        match c {
+            '.' => _ = self.emit(Inst::Any),
+            '^' => _ = self.emit(Inst::AssertStart),
+            '$' => _ = self.emit(Inst::AssertEnd),
+            '*' | '+' | '?' => {
+                return Err(RegexError::new(RegexErrorKind::NothingToRepeat, offset))
+            }
+            '(' => {
+                if let Err(err) = self.alternation() {
+                    return Err(err);
+                }
+                if !matches!(self.peek(), Some(')')) {
+                    return Err(RegexError::new(RegexErrorKind::UnclosedGroup, offset));
+                }
+                self.pos += 1;
+            }
+            '[' => return self.class(offset),
+            '\\' => match self.escape(offset) {
+                Ok(Escape::Char(c)) => _ = self.emit(Inst::Char(c)),
+                Ok(Escape::Class(ranges, negated)) => {
+                    let start = self.ranges.len();
+                    self.push_ranges(ranges);
+                    self.emit(Inst::Class {
+                        start,
+                        end: self.ranges.len(),
+                        negated,
+                    });
+                }
+                Err(err) => return Err(err),
+            },
+            c => _ = self.emit(Inst::Char(c)),
+        }
+        Ok(())
+    }
+    /// Parses the escape after the `\` found at `offset`
+    const fn escape(&mut self, offset: usize) -> Result<Escape, RegexError> {
+        let (c, next) = match char_at(self.pattern, self.pos) {
+            Some(found) => found,
+            None => return Err(RegexError::new(RegexErrorKind::TrailingEscape, offset)),
+        };
+        self.pos = next;
+        Ok(match c {
+            'n' => Escape::Char('\n'),
+            't' => Escape::Char('\t'),
+            'r' => Escape::Char('\r'),
+            'd' => Escape::Class(DIGIT, false),
+            'w' => Escape::Class(WORD, false),
+            's' => Escape::Class(SPACE, false),
+            'D' => Escape::Class(DIGIT, true),
+            'W' => Escape::Class(WORD, true),
+            'S' => Escape::Class(SPACE, true),
+            c if c.is_ascii_punctuation() => Escape::Char(c),
+            _ => return Err(RegexError::new(RegexErrorKind::InvalidEscape, offset)),
+        })
+    }
+    const fn push_ranges(&mut self, ranges: &[(char, char)]) {
+        let mut idx = 0;
+        while idx < ranges.len() {
+            self.ranges = self.ranges.push(ranges[idx]);
+            idx += 1;
+        }
+    }
+    /// Parses a class member, a negated escape class is only allowed outside of classes.
+    const fn class_member(&mut self, open: usize) -> Result<Escape, RegexError> {
+        let offset = self.pos;
+        match char_at(self.pattern, self.pos) {
+            Some(('\\', next)) => {
+                self.pos = next;
+                match self.escape(offset) {
+                    Ok(Escape::Class(_, true)) => {
+                        Err(RegexError::new(RegexErrorKind::InvalidEscape, offset))
+                    }
+                    member => member,
+                }
+            }
+            Some((c, next)) => {
+                self.pos = next;
+                Ok(Escape::Char(c))
+            }
+            None => Err(RegexError::new(RegexErrorKind::UnterminatedClass, open)),
+        }
+    }
+    /// Parses the class opened at `open`
+    const fn class(&mut self, open: usize) -> Result<(), RegexError> {
+        let negated = matches!(self.peek(), Some('^'));
+        if negated {
+            self.pos += 1;
+        }
+        let start = self.ranges.len();
+        let mut first = true;
+        loop {
+            if !first && matches!(self.peek(), Some(']')) {
+                self.pos += 1;
+                break;
+            }
+            first = false;
+            let offset = self.pos;
+            let lo = match self.class_member(open) {
+                Ok(Escape::Char(c)) => c,
+                Ok(Escape::Class(ranges, _)) => {
+                    self.push_ranges(ranges);
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
+            let bytes = self.pattern.as_bytes();
+            let mut hi = lo;
+            if self.pos + 1 < bytes.len() && bytes[self.pos] == b'-' && bytes[self.pos + 1] != b']'
+            {
+                self.pos += 1;
+                hi = match self.class_member(open) {
+                    Ok(Escape::Char(c)) if c >= lo => c,
+                    Ok(_) => return Err(RegexError::new(RegexErrorKind::InvalidRange, offset)),
+                    Err(err) => return Err(err),
+                };
+            }
+            self.ranges = self.ranges.push((lo, hi));
+        }
+        self.emit(Inst::Class {
+            start,
+            end: self.ranges.len(),
+            negated,
+        });
+        Ok(())
+    }
+}
+
+const fn split(preferred: usize, other: usize, lazy: bool) -> Inst {
+    if lazy {
+        Inst::Split(other, preferred)
+    } else {
+        Inst::Split(preferred, other)
+    }
+}
+
+#[cfg(test)]
+mod regex_test {
+    use super::*;
+
+    const IDENT: KRegex<32> = KRegex::parse("^[a-zA-Z_][a-zA-Z0-9_]*$");
+    const _: () = {
+        assert!(IDENT.is_match("snake_case_2"));
+        assert!(!IDENT.is_match("2fast"));
+        assert!(matches!(
+            KRegex::<8>::try_parse("a(b"),
+            Err(RegexError {
+                kind: RegexErrorKind::UnclosedGroup,
+                offset: 1
+            })
+        ));
+    };
+
+    fn find(pattern: &str, text: &str) -> Option<(usize, usize)> {
+        match KRegex::<64>::try_parse(pattern) {
+            Ok(regex) => regex.find(text),
+            Err(err) => panic!("{pattern}: {err}"),
+        }
+    }
+
+    #[test]
+    fn matching() {
+        for (pattern, text, expected) in [
+            ("", "abc", Some((0, 0))),
+            ("abc", "xxabcxx", Some((2, 5))),
+            ("a.c", "a\nc abc", Some((4, 7))),
+            ("a*", "baaa", Some((0, 0))),
+            ("a+", "baaa", Some((1, 4))),
+            ("a+?", "baaa", Some((1, 2))),
+            ("ba*?", "baaa", Some((0, 1))),
+            ("colou?r", "my color", Some((3, 8))),
+            ("a|ab", "ab", Some((0, 1))),
+            ("ab|a", "ab", Some((0, 2))),
+            ("(a|b)+c", "xxababcx", Some((2, 7))),
+            ("(a*)*b", "aaab", Some((0, 4))),
+            ("(|a)+", "aa", Some((0, 0))),
+            ("(a??)*", "aa", Some((0, 0))),
+            ("(a*?)*", "aa", Some((0, 0))),
+            ("(b*?|a)*", "aa", Some((0, 0))),
+            ("(a|b*?)*", "bb", Some((0, 0))),
+            ("(a?)*b", "aab", Some((0, 3))),
+            ("(a*)*?b", "aab", Some((0, 3))),
+            ("^ab", "cab", None),
+            ("ab$", "abab", Some((2, 4))),
+            ("^$", "", Some((0, 0))),
+            ("x|^a", "ba", None),
+            ("[^a-c]+", "abcdefa", Some((3, 6))),
+            ("[]a]+", "x]a]", Some((1, 4))),
+            ("[a-]+", "x-a", Some((1, 3))),
+            (r"[\d.]+", "v1.20", Some((1, 5))),
+            (r"\w+@\w+\.com", "mail: me@host.com", Some((6, 17))),
+            (r"\s\S", "a  b", Some((2, 4))),
+            (r"\D+", "12ab3", Some((2, 4))),
+            (r"\(\*\)", "f(*)", Some((1, 4))),
+            ("é+", "caféé", Some((3, 7))),
+            ("[α-ω]+", "abγδ", Some((2, 6))),
+            ("a{2}", "a{2}", Some((0, 4))),
+        ] {
+            assert_eq!(find(pattern, text), expected, "{pattern} {text:?}");
+        }
+    }
+
+    #[test]
+    fn errors() {
+        for (pattern, kind, offset) in [
+            ("(ab", RegexErrorKind::UnclosedGroup, 0),
+            ("a)b", RegexErrorKind::UnmatchedParen, 1),
+            ("*a", RegexErrorKind::NothingToRepeat, 0),
+            ("a|+", RegexErrorKind::NothingToRepeat, 2),
+            ("(?:a)", RegexErrorKind::NothingToRepeat, 1),
+            ("[ab", RegexErrorKind::UnterminatedClass, 0),
+            ("x[z-a]", RegexErrorKind::InvalidRange, 2),
+            (r"[a-\d]", RegexErrorKind::InvalidRange, 1),
+            (r"[\D]", RegexErrorKind::InvalidEscape, 1),
+            (r"\b", RegexErrorKind::InvalidEscape, 0),
+            ("ab\\", RegexErrorKind::TrailingEscape, 2),
+        ] {
+            assert_eq!(
+                KRegex::<16>::try_parse(pattern),
+                Err(RegexError::new(kind, offset)),
+                "{pattern}"
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn overflow() {
+        let _ = KRegex::<4>::try_parse("abcdef");
+    }
+}
@@ -0,0 +1,703 @@
+//! Templates are text with `{key}` placeholders, in the format of indicatif progress bars. A
+//! placeholder can have a spec after a `:`, made of an optional alignment `<`, `^` or `>`, an
+//! optional width, an optional `!` to truncate longer values with `…`, and an optional style
+//! after a `.`, like `{msg:^20!.bold.red.on_blue}`.
+//!
+//! Styles are dot separated colors and attributes: `bold`, `dim`, `italic`, `underlined`,
+//! `blink`, `reverse` and `hidden`, a [`Color`](crate::color::Color) for the foreground, `on_`
+//! followed by a [`Color`](crate::color::Color) for the background, and `bright` and `on_bright`
+//! to use the bright variant of the 8 ANSI colors. A second style can follow a `/`, like
+//! `{bar:40.cyan/blue}`, widgets drawing two parts use it for the second one.
+//!
+//! Keys are made of ASCII alphanumerics, `_` and `-`, and `{{` and `}}` are literal braces.
+//!
+//! ```rust
+//! use konster::template::Template;
+//!
+//! const STATUS: Template = Template::parse("{{{name:>6}}} {msg:.bold.#ff8700}");
+//! const _: () = assert!(STATUS.parts().len() == 5);
+//! assert_eq!(
+//!     STATUS.render_plain(&[("name", "build"), ("msg", "done")]),
+//!     "{ build} done"
+//! );
+//! assert_eq!(
+//!     STATUS.render(&[("name", "build"), ("msg", "done")]),
+//!     "{ build} \x1b[38;2;255;135;0;1mdone\x1b[0m"
+//! );
+//! ```
+use crate::color::{Color, ColorErrorKind};
+use crate::error::impl_error;
+use crate::kstr::{bytes_eq, parse_u64, Alignment};
+use crate::kvec::KVec;
+
+/// Error returned when parsing a malformed template
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TemplateError {
+    /// What is wrong with the template
+    pub kind: TemplateErrorKind,
+    /// Byte index in the input where the error was found
+    pub offset: usize,
+}
+
+/// The different ways a template can be malformed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TemplateErrorKind {
+    /// A `{` without its closing `}`, the offset is the one of the `{`
+    UnclosedPlaceholder,
+    /// A `}` that doesn't close a placeholder and is not escaped as `}}`
+    UnmatchedBrace,
+    /// A key that is empty or has chars other than ASCII alphanumerics, `_` and `-`
+    InvalidKey,
+    /// A width that doesn't fit in an `u16`
+    InvalidWidth,
+    /// Something other than an alignment, a width, a `!` or a style in a spec
+    InvalidSpec,
+    /// A style part that is neither an attribute nor a color
+    UnknownStyle,
+    /// A malformed hex, `rgb()` or palette color
+    InvalidColor(ColorErrorKind),
+}
+
+impl TemplateErrorKind {
+    /// Returns a description of the error.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            TemplateErrorKind::UnclosedPlaceholder => "unclosed placeholder",
+            TemplateErrorKind::UnmatchedBrace => "unmatched `}`",
+            TemplateErrorKind::InvalidKey => "invalid placeholder key",
+            TemplateErrorKind::InvalidWidth => "width out of range",
+            TemplateErrorKind::InvalidSpec => "invalid placeholder spec",
+            TemplateErrorKind::UnknownStyle => "unknown style",
+            TemplateErrorKind::InvalidColor(kind) => kind.as_str(),
+        }
+    }
+}
+
+impl TemplateError {
+    const fn new(kind: TemplateErrorKind, offset: usize) -> Self {
+        Self { kind, offset }
+    }
+}
+impl_error!(TemplateError, " at byte " offset);
+
+/// A text attribute of a [`Style`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Attribute {
+    /// Bold or increased intensity
+    Bold,
+    /// Faint or decreased intensity
+    Dim,
+    /// Italic
+    Italic,
+    /// Underlined
+    Underlined,
+    /// Slow blink
+    Blink,
+    /// Swapped foreground and background colors
+    Reverse,
+    /// Invisible text
+    Hidden,
+}
+
+impl Attribute {
+    /// Returns the SGR code enabling the attribute
+    const fn sgr(self) -> u8 {
+        match self {
+            Attribute::Bold => 1,
+            Attribute::Dim => 2,
+            Attribute::Italic => 3,
+            Attribute::Underlined => 4,
+            Attribute::Blink => 5,
+            Attribute::Reverse => 7,
+            Attribute::Hidden => 8,
+        }
+    }
+}
+
+/// Every attribute in SGR order
+const ATTRIBUTES: [Attribute; 7] = [
+    Attribute::Bold,
+    Attribute::Dim,
+    Attribute::Italic,
+    Attribute::Underlined,
+    Attribute::Blink,
+    Attribute::Reverse,
+    Attribute::Hidden,
+];
+
+/// Colors and attributes of a placeholder
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Style {
+    /// The foreground color
+    pub fg: Option<Color>,
+    /// The background color
+    pub bg: Option<Color>,
+    /// Whether the foreground uses the bright variant of the 8 ANSI colors
+    pub fg_bright: bool,
+    /// Whether the background uses the bright variant of the 8 ANSI colors
+    pub bg_bright: bool,
+    /// The attributes, one bit per [`Attribute`]
+    attrs: u8,
+}
+
+impl Style {
+    /// Returns a style without colors or attributes.
+    pub const fn new() -> Self {
+        Self {
+            fg: None,
+            bg: None,
+            fg_bright: false,
+            bg_bright: false,
+            attrs: 0,
+        }
+    }
+
+    /// Parses a dotted style like `bold.red.on_blue`.
+    /// # Panics: panic if the style is malformed
+    pub const fn parse(s: &str) -> Self {
+        match Self::try_parse(s) {
+            Ok(style) => style,
+            Err(err) => err.panic(),
+        }
+    }
+
+    /// Parses a dotted style like `bold.red.on_blue`, returning an error if it is malformed.
+    pub const fn try_parse(s: &str) -> Result<Self, TemplateError> {
+        let bytes = s.as_bytes();
+        let mut style = Self::new();
+        let mut start = 0;
+        loop {
+            let mut end = start;
+            while end < bytes.len() && bytes[end] != b'.' {
+                end += 1;
+            }
+            let part = s.split_at(end).0.split_at(start).1;
+            style = match part.as_bytes() {
+                b"bold" => style.attr(Attribute::Bold),
+                b"dim" => style.attr(Attribute::Dim),
+                b"italic" => style.attr(Attribute::Italic),
+                b"underlined" => style.attr(Attribute::Underlined),
+                b"blink" => style.attr(Attribute::Blink),
+                b"reverse" => style.attr(Attribute::Reverse),
+                b"hidden" => style.attr(Attribute::Hidden),
+                b"bright" => style.bright(),
+                b"on_bright" => style.on_bright(),
+                [b'o', b'n', b'_', ..] => match Color::try_parse(part.split_at(3).1) {
+                    Ok(color) => style.bg(color),
+                    Err(err) => return Err(color_error(err.kind, start, start + 3 + err.offset)),
+                },
+                _ => match Color::try_parse(part) {
+                    Ok(color) => style.fg(color),
+                    Err(err) => return Err(color_error(err.kind, start, start + err.offset)),
+                },
+            };
+            if end == bytes.len() {
+                return Ok(style);
+            }
+            start = end + 1;
+        }
+    }
+
+    /// Sets the foreground color.
+    #[must_use]
+    pub const fn fg(mut self, color: Color) -> Self {
+        self.fg = Some(color);
+        self
+    }
+
+    /// Sets the background color.
+    #[must_use]
+    pub const fn bg(mut self, color: Color) -> Self {
+        self.bg = Some(color);
+        self
+    }
+
+    /// Uses the bright variant of the 8 ANSI colors for the foreground.
+    #[must_use]
+    pub const fn bright(mut self) -> Self {
+        self.fg_bright = true;
+        self
+    }
+
+    /// Uses the bright variant of the 8 ANSI colors for the background.
+    #[must_use]
+    pub const fn on_bright(mut self) -> Self {
+        self.bg_bright = true;
+        self
+    }
+
+    /// Adds an attribute.
+    #[must_use]
+    pub const fn attr(mut self, attr: Attribute) -> Self {
+        self.attrs |= 1 << attr as u8;
+        self
+    }
+
+    /// Returns true if the style has the attribute
+    pub const fn has_attr(&self, attr: Attribute) -> bool {
+        self.attrs & (1 << attr as u8) != 0
+    }
+
+    /// Returns true if the style has no colors and no attributes
+    pub const fn is_plain(&self) -> bool {
+        self.fg.is_none() && self.bg.is_none() && self.attrs == 0
+    }
+
+    /// Returns the text wrapped in the ANSI escape sequences of the style, plain styles return it as is.
+    pub fn apply(&self, text: &str) -> String {
+        if self.is_plain() {
+            return text.to_owned();
+        }
+        let mut codes = Vec::new();
+        if let Some(color) = self.fg {
+            codes.push(color_sgr(color, self.fg_bright, 30));
+        }
+        if let Some(color) = self.bg {
+            codes.push(color_sgr(color, self.bg_bright, 40));
+        }
+        for attr in ATTRIBUTES {
+            if self.has_attr(attr) {
+                codes.push(attr.sgr().to_string());
+            }
+        }
+        format!("\x1b[{}m{text}\x1b[0m", codes.join(";"))
+    }
+}
+
+/// Returns the SGR parameters of a color, `base` is 30 for the foreground and 40 for the background.
+fn color_sgr(color: Color, bright: bool, base: u8) -> String {
+    match color {
+        Color::Color256(n) => format!("{};5;{n}", base + 8),
+        Color::Rgb(r, g, b) => format!("{};2;{r};{g};{b}", base + 8),
+        color => match color.to_ansi256() {
+            n @ 0..=7 if bright => (base + 60 + n).to_string(),
+            n @ 0..=7 => (base + n).to_string(),
+            n => (base + 60 + n - 8).to_string(),
+        },
+    }
+}
+
+/// Maps the error of a style color, unknown names are unknown styles.
+const fn color_error(kind: ColorErrorKind, part: usize, offset: usize) -> TemplateError {
+    match kind {
+        ColorErrorKind::UnknownColor => TemplateError::new(TemplateErrorKind::UnknownStyle, part),
+        kind => TemplateError::new(TemplateErrorKind::InvalidColor(kind), offset),
+    }
+}
+
+/// A `{key:spec}` placeholder
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Placeholder<'a> {
+    /// The key of the value
+    pub key: &'a str,
+    /// How the value is aligned in its width, left by default
+    pub align: Alignment,
+    /// The minimum width of the value in chars
+    pub width: Option<u16>,
+    /// Whether values wider than `width` are truncated
+    pub truncate: bool,
+    /// The style of the value
+    pub style: Option<Style>,
+    /// The style of the second part of widgets drawing two parts
+    pub alt_style: Option<Style>,
+}
+
+/// A part of a template
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Part<'a> {
+    /// Literal text, with escaped braces already unescaped
+    Literal(&'a str),
+    /// A placeholder replaced by a value
+    Placeholder(Placeholder<'a>),
+}
+
+/// A parsed template of at most `N` parts, borrowing its text
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Template<'a, const N: usize = 16> {
+    parts: KVec<Part<'a>, N>,
+}
+
+impl<'a, const N: usize> Template<'a, N> {
+    /// Parses a template.
+    /// # Panics: panic if the template is malformed or has more than `N` parts
+    pub const fn parse(s: &'a str) -> Self {
+        match Self::try_parse(s) {
+            Ok(template) => template,
+            Err(err) => err.panic(),
+        }
+    }
+
+    /// Parses a template, returning an error if it is malformed.
+    /// # Panics: panic if the template has more than `N` parts
+    pub const fn try_parse(s: &'a str) -> Result<Self, TemplateError> {
+        let bytes = s.as_bytes();
+        let mut parts = KVec::new(Part::Literal(""));
+        let mut start = 0;
+        let mut idx = 0;
+        while idx < bytes.len() {
+            let escaped = idx + 1 < bytes.len() && bytes[idx + 1] == bytes[idx];
+            match bytes[idx] {
+                b'{' | b'}' if escaped => {
+                    // Keep the first brace in the literal and skip the second one
+                    parts = push_literal(parts, s, start, idx + 1);
+                    idx += 2;
+                    start = idx;
+                }
+                b'}' => return Err(TemplateError::new(TemplateErrorKind::UnmatchedBrace, idx)),
+                b'{' => {
+                    parts = push_literal(parts, s, start, idx);
+                    let mut close = idx + 1;
+                    while close < bytes.len() && bytes[close] != b'}' && bytes[close] != b'{' {
+                        close += 1;
+                    }
+                    if close == bytes.len() || bytes[close] != b'}' {
+                        return Err(TemplateError::new(
+                            TemplateErrorKind::UnclosedPlaceholder,
+                            idx,
+                        ));
+                    }
+                    match placeholder(s, idx + 1, close) {
+                        Ok(placeholder) => parts = parts.push(Part::Placeholder(placeholder)),
+                        Err(err) => return Err(err),
+                    }
+                    idx = close + 1;
+                    start = idx;
+                }
+                _ => idx += 1,
+            }
+        }
+        parts = push_literal(parts, s, start, bytes.len());
+        Ok(Self { parts })
+    }
+
+    /// Returns the parts in the order of the template
+    pub const fn parts(&self) -> &[Part<'a>] {
+        self.parts.as_slice()
+    }
+
+    /// Returns the first placeholder with the key, or None if there is none.
+    pub const fn placeholder(&self, key: &str) -> Option<&Placeholder<'a>> {
+        let mut idx = 0;
+        while idx < self.parts.len() {
+            if let Part::Placeholder(placeholder) = self.parts.get_unchecked(idx) {
+                if bytes_eq(placeholder.key.as_bytes(), key.as_bytes()) {
+                    return Some(placeholder);
+                }
+            }
+            idx += 1;
+        }
+        None
+    }
+
+    /// Returns the template with the placeholders replaced by their value in `values`, padded,
+    /// truncated and styled with ANSI escape sequences.
+    ///
+    /// Keys missing from `values` are replaced by an empty value.
+    pub fn render(&self, values: &[(&str, &str)]) -> String {
+        self.render_with(values, true)
+    }
+
+    /// Returns the template with the placeholders replaced by their value in `values`, padded and
+    /// truncated but without styles.
+    ///
+    /// Keys missing from `values` are replaced by an empty value.
+    pub fn render_plain(&self, values: &[(&str, &str)]) -> String {
+        self.render_with(values, false)
+    }
+
+    fn render_with(&self, values: &[(&str, &str)], styled: bool) -> String {
+        let mut out = String::new();
+        for part in self.parts() {
+            let placeholder = match part {
+                Part::Literal(text) => {
+                    out.push_str(text);
+                    continue;
+                }
+                Part::Placeholder(placeholder) => placeholder,
+            };
+            let value = values
+                .iter()
+                .find(|(key, _)| *key == placeholder.key)
+                .map_or("", |(_, value)| value);
+            let value = fit(placeholder, value);
+            match placeholder.style {
+                Some(style) if styled => out.push_str(&style.apply(&value)),
+                _ => out.push_str(&value),
+            }
+        }
+        out
+    }
+}
+
+/// Appends the non empty literal `s[start..end]`.
+const fn push_literal<'a, const N: usize>(
+    parts: KVec<Part<'a>, N>,
+    s: &'a str,
+    start: usize,
+    end: usize,
+) -> KVec<Part<'a>, N> {
+    if start == end {
+        return parts;
+    }
+    parts.push(Part::Literal(s.split_at(end).0.split_at(start).1))
+}
+
+/// Parses the placeholder between the braces at `start - 1` and `end`.
+const fn placeholder(s: &str, start: usize, end: usize) -> Result<Placeholder<'_>, TemplateError> {
+    let bytes = s.as_bytes();
+    let mut idx = start;
+    while idx < end && matches!(bytes[idx], b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'_' | b'-') {
+        idx += 1;
+    }
+    if idx == start || (idx < end && bytes[idx] != b':') {
+        return Err(TemplateError::new(TemplateErrorKind::InvalidKey, idx));
+    }
+    let mut placeholder = Placeholder {
+        key: s.split_at(idx).0.split_at(start).1,
+        align: Alignment::Left,
+        width: None,
+        truncate: false,
+        style: None,
+        alt_style: None,
+    };
+    if idx == end {
+        return Ok(placeholder);
+    }
+    idx += 1;
+    let align = if idx < end {
+        match bytes[idx] {
+            b'<' => Some(Alignment::Left),
+            b'^' => Some(Alignment::Center),
+            b'>' => Some(Alignment::Right),
+            _ => None,
+        }
+    } else {
+        None
+    };
+    if let Some(align) = align {
+        placeholder.align = align;
+        idx += 1;
+    }
+    let width_start = idx;
+    while idx < end && bytes[idx].is_ascii_digit() {
+        idx += 1;
+    }
+    if idx > width_start {
+        match parse_u64(s.split_at(idx).0.split_at(width_start).1) {
+            Ok(width) if width <= u16::MAX as u64 => placeholder.width = Some(width as u16),
+            _ => {
+                return Err(TemplateError::new(
+                    TemplateErrorKind::InvalidWidth,
+                    width_start,
+                ))
+            }
+        }
+    }
+    if idx < end && bytes[idx] == b'!' {
+        placeholder.truncate = true;
+        idx += 1;
+    }
+    if idx < end && bytes[idx] == b'.' {
+        idx += 1;
+        let mut slash = idx;
+        while slash < end && bytes[slash] != b'/' {
+            slash += 1;
+        }
+        placeholder.style = match style(s, idx, slash) {
+            Ok(style) => Some(style),
+            Err(err) => return Err(err),
+        };
+        if slash < end {
+            placeholder.alt_style = match style(s, slash + 1, end) {
+                Ok(style) => Some(style),
+                Err(err) => return Err(err),
+            };
+        }
+        idx = end;
+    }
+    if idx < end {
+        return Err(TemplateError::new(TemplateErrorKind::InvalidSpec, idx));
+    }
+    Ok(placeholder)
+}
+
+/// Parses the style `s[start..end]`, with error offsets in `s`.
+const fn style(s: &str, start: usize, end: usize) -> Result<Style, TemplateError> {
+    match Style::try_parse(s.split_at(end).0.split_at(start).1) {
+        Ok(style) => Ok(style),
+        Err(err) => Err(TemplateError::new(err.kind, start + err.offset)),
+    }
+}
+
+/// Returns the value padded to the placeholder width, and truncated if it is too wide.
+fn fit(placeholder: &Placeholder<'_>, value: &str) -> String {
+    let width = match placeholder.width {
+        Some(width) => width as usize,
+        None => return value.to_owned(),
+    };
+    if placeholder.truncate && value.chars().count() > width {
+        return match width.checked_sub(1) {
+            Some(keep) => {
+                let end = value
+                    .char_indices()
+                    .nth(keep)
+                    .map_or(value.len(), |(i, _)| i);
+                format!("{}…", &value[..end])
+            }
+            None => String::new(),
+        };
+    }
+    match placeholder.align {
+        Alignment::Left => format!("{value:<width$}"),
+        Alignment::Center => format!("{value:^width$}"),
+        Alignment::Right => format!("{value:>width$}"),
+    }
+}
+
+#[cfg(test)]
+mod template_test {
+    use super::*;
+
+    const BAR: Template = Template::parse("[{elapsed}] {bar:40.cyan/blue} {pos:>7}/{len:7}");
+    const _: () = {
+        assert!(BAR.parts().len() == 8);
+        assert!(match BAR.placeholder("bar") {
+            Some(bar) => matches!(bar.width, Some(40)) && bar.alt_style.is_some(),
+            None => false,
+        });
+        assert!(Style::parse("bold.on_red").has_attr(Attribute::Bold));
+    };
+
+    #[test]
+    fn parts() {
+        let template: Template = Template::parse("a{{b}}{key:^12!.bold.bright.red.on_#000/dim}\n");
+        assert_eq!(
+            template.parts(),
+            [
+                Part::Literal("a{"),
+                Part::Literal("b}"),
+                Part::Placeholder(Placeholder {
+                    key: "key",
+                    align: Alignment::Center,
+                    width: Some(12),
+                    truncate: true,
+                    style: Some(
+                        Style::new()
+                            .attr(Attribute::Bold)
+                            .bright()
+                            .fg(Color::Red)
+                            .bg(Color::Rgb(0, 0, 0))
+                    ),
+                    alt_style: Some(Style::new().attr(Attribute::Dim)),
+                }),
+                Part::Literal("\n"),
+            ]
+        );
+        let template: Template = Template::parse("{a}{b:}{c:>}{d:5}{e:!}");
+        let placeholders: Vec<_> = template
+            .parts()
+            .iter()
+            .map(|part| match part {
+                Part::Placeholder(p) => (p.key, p.align, p.width, p.truncate, p.style),
+                Part::Literal(_) => unreachable!(),
+            })
+            .collect();
+        assert_eq!(
+            placeholders,
+            [
+                ("a", Alignment::Left, None, false, None),
+                ("b", Alignment::Left, None, false, None),
+                ("c", Alignment::Right, None, false, None),
+                ("d", Alignment::Left, Some(5), false, None),
+                ("e", Alignment::Left, None, true, None),
+            ]
+        );
+        let empty: Template = Template::parse("");
+        assert!(empty.parts().is_empty());
+    }
+
+    #[test]
+    fn errors() {
+        for (s, kind, offset) in [
+            ("abc {key", TemplateErrorKind::UnclosedPlaceholder, 4),
+            ("{a{b}", TemplateErrorKind::UnclosedPlaceholder, 0),
+            ("a } b", TemplateErrorKind::UnmatchedBrace, 2),
+            ("{a}}", TemplateErrorKind::UnmatchedBrace, 3),
+            ("{}", TemplateErrorKind::InvalidKey, 1),
+            ("{:5}", TemplateErrorKind::InvalidKey, 1),
+            ("{ a }", TemplateErrorKind::InvalidKey, 1),
+            ("{a b}", TemplateErrorKind::InvalidKey, 2),
+            ("{a:70000}", TemplateErrorKind::InvalidWidth, 3),
+            ("{a:<5x}", TemplateErrorKind::InvalidSpec, 5),
+            ("{a:5!!}", TemplateErrorKind::InvalidSpec, 5),
+            ("{a:.red.shiny}", TemplateErrorKind::UnknownStyle, 8),
+            ("{a:.red/}", TemplateErrorKind::UnknownStyle, 8),
+            ("{a:.red..bold}", TemplateErrorKind::UnknownStyle, 8),
+            ("{a:.on_rebeccapink}", TemplateErrorKind::UnknownStyle, 4),
+            (
+                "{a:.bold.#12345g}",
+                TemplateErrorKind::InvalidColor(ColorErrorKind::InvalidHex),
+                15,
+            ),
+            (
+                "{a:.on_rgb(1, 2, 300)}",
+                TemplateErrorKind::InvalidColor(ColorErrorKind::OutOfRange),
+                17,
+            ),
+            (
+                "{a:.red/256}",
+                TemplateErrorKind::InvalidColor(ColorErrorKind::OutOfRange),
+                8,
+            ),
+        ] {
+            assert_eq!(
+                Template::<16>::try_parse(s),
+                Err(TemplateError::new(kind, offset)),
+                "{s:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn render() {
+        let template: Template = Template::parse("{a:>5}|{b:^6}|{c:4!}|{d:3}|{e:0!}|{missing}.");
+        let values = [
+            ("a", "ab"),
+            ("b", "xyz"),
+            ("c", "héllo"),
+            ("d", "long"),
+            ("e", "gone"),
+        ];
+        assert_eq!(template.render(&values), "   ab| xyz  |hél…|long||.");
+        assert_eq!(
+            BAR.render_plain(&[("elapsed", "00:01"), ("bar", "###"), ("pos", "3")]),
+            "[00:01] ###                                            3/       "
+        );
+    }
+
+    #[test]
+    fn styles() {
+        for (style, expected) in [
+            ("red", "\x1b[31mx\x1b[0m"),
+            ("bright.red", "\x1b[91mx\x1b[0m"),
+            ("bright_red.on_blue.on_bright", "\x1b[91;104mx\x1b[0m"),
+            ("208.on_rgb(1,2,3)", "\x1b[38;5;208;48;2;1;2;3mx\x1b[0m"),
+            ("hidden.underlined.bold", "\x1b[1;4;8mx\x1b[0m"),
+        ] {
+            assert_eq!(Style::parse(style).apply("x"), expected, "{style}");
+        }
+        let template: Template = Template::parse("{a:.bold}{b}");
+        assert_eq!(
+            template.render(&[("a", "x"), ("b", "y")]),
+            "\x1b[1mx\x1b[0my"
+        );
+        assert_eq!(template.render_plain(&[("a", "x"), ("b", "y")]), "xy");
+        assert!(Style::new().is_plain());
+    }
+
+    #[test]
+    #[should_panic]
+    fn malformed() {
+        let _: Template = Template::parse("{unclosed");
+    }
+}
@@ -0,0 +1,526 @@
+//! Timestamps follow RFC 3339, like `2026-10-18T12:00:00Z` or `2026-10-18 14:00:00.5+02:00`.
+//! The date is checked against the calendar, fractional seconds are checked then dropped, and a
+//! leap second `:60` counts as the first second of the next minute like in Unix time.
+//!
+//! Durations are numbers followed by units, like `1h30m`, `1h 30m 05s` or `1.5 days`, with the
+//! units in decreasing order. Units are `ns`, `us`/`µs`, `ms`, `s`, `m`, `h`, `d` and `w`, and
+//! their English names like `sec`, `minutes` or `hour`.
+//!
+//! ```rust
+//! use konster::time::{format_duration, parse_duration, parse_rfc3339};
+//! use std::time::Duration;
+//!
+//! const RELEASE: i64 = parse_rfc3339("2026-10-18T12:00:00Z");
+//! const TIMEOUT: Duration = parse_duration("1h30m5s");
+//! assert_eq!(RELEASE, 1_792_324_800);
+//! assert_eq!(TIMEOUT, Duration::from_secs(5405));
+//! assert_eq!(format_duration::<16>(TIMEOUT).as_str(), "1h 30m 05s");
+//! ```
+use crate::error::impl_error;
+use crate::kstr::{parse_u64, skip_spaces, KStr};
+use std::time::Duration;
+
+/// Error returned when parsing a malformed timestamp or duration
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimeError {
+    /// What is wrong with the timestamp or duration
+    pub kind: TimeErrorKind,
+    /// Byte index in the input where the error was found
+    pub offset: usize,
+}
+
+/// The different ways a timestamp or duration can be malformed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimeErrorKind {
+    /// A char that is not allowed here
+    UnexpectedChar,
+    /// The input ends too early
+    UnexpectedEnd,
+    /// A month that is not from 01 to 12
+    InvalidMonth,
+    /// A day that is not in the month
+    InvalidDay,
+    /// An hour, minute or second out of range
+    InvalidTime,
+    /// A UTC offset out of range
+    InvalidOffset,
+    /// A duration unit that is not known
+    UnknownUnit,
+    /// A duration unit that is not smaller than the previous one
+    UnitOrder,
+    /// A duration that doesn't fit in a [`Duration`]
+    Overflow,
+}
+
+impl TimeErrorKind {
+    /// Returns a description of the error.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            TimeErrorKind::UnexpectedChar => "unexpected char",
+            TimeErrorKind::UnexpectedEnd => "unexpected end",
+            TimeErrorKind::InvalidMonth => "invalid month",
+            TimeErrorKind::InvalidDay => "invalid day",
+            TimeErrorKind::InvalidTime => "invalid time",
+            TimeErrorKind::InvalidOffset => "invalid UTC offset",
+            TimeErrorKind::UnknownUnit => "unknown unit",
+            TimeErrorKind::UnitOrder => "unit out of order",
+            TimeErrorKind::Overflow => "duration out of range",
+        }
+    }
+}
+
+impl TimeError {
+    const fn new(kind: TimeErrorKind, offset: usize) -> Self {
+        Self { kind, offset }
+    }
+    /// Builds the error for an unexpected char or end at `offset`.
+    const fn unexpected(s: &str, offset: usize) -> Self {
+        if offset < s.len() {
+            Self::new(TimeErrorKind::UnexpectedChar, offset)
+        } else {
+            Self::new(TimeErrorKind::UnexpectedEnd, offset)
+        }
+    }
+}
+impl_error!(TimeError, " at byte " offset);
+
+/// Parses an RFC 3339 timestamp into seconds since the Unix epoch.
+/// # Panics: panic if the timestamp is malformed
+pub const fn parse_rfc3339(s: &str) -> i64 {
+    match try_parse_rfc3339(s) {
+        Ok(secs) => secs,
+        Err(err) => err.panic(),
+    }
+}
+
+/// Parses an RFC 3339 timestamp into seconds since the Unix epoch, returning an error if it is malformed.
+pub const fn try_parse_rfc3339(s: &str) -> Result<i64, TimeError> {
+    let bytes = s.as_bytes();
+    let mut fields = [0; 6];
+    // Each field is its digits count and the separator after it
+    let layout: [(usize, u8); 6] = [
+        (4, b'-'),
+        (2, b'-'),
+        (2, b'T'),
+        (2, b':'),
+        (2, b':'),
+        (2, 0),
+    ];
+    let mut idx = 0;
+    let mut field = 0;
+    while field < 6 {
+        fields[field] = match digits(s, idx, layout[field].0) {
+            Ok(n) => n,
+            Err(err) => return Err(err),
+        };
+        idx += layout[field].0;
+        let sep = layout[field].1;
+        if sep != 0 {
+            let ok = idx < bytes.len()
+                && (bytes[idx] == sep || (sep == b'T' && matches!(bytes[idx], b't' | b' ')));
+            if !ok {
+                return Err(TimeError::unexpected(s, idx));
+            }
+            idx += 1;
+        }
+        field += 1;
+    }
+    let [year, month, day, hour, minute, second] = fields;
+    if month < 1 || month > 12 {
+        return Err(TimeError::new(TimeErrorKind::InvalidMonth, 5));
+    }
+    if day < 1 || day > days_in_month(year, month) {
+        return Err(TimeError::new(TimeErrorKind::InvalidDay, 8));
+    }
+    if hour > 23 {
+        return Err(TimeError::new(TimeErrorKind::InvalidTime, 11));
+    }
+    if minute > 59 {
+        return Err(TimeError::new(TimeErrorKind::InvalidTime, 14));
+    }
+    if second > 60 {
+        return Err(TimeError::new(TimeErrorKind::InvalidTime, 17));
+    }
+    if idx < bytes.len() && bytes[idx] == b'.' {
+        idx += 1;
+        let start = idx;
+        while idx < bytes.len() && bytes[idx].is_ascii_digit() {
+            idx += 1;
+        }
+        if idx == start {
+            return Err(TimeError::unexpected(s, idx));
+        }
+    }
+    let offset = if idx < bytes.len() && matches!(bytes[idx], b'Z' | b'z') {
+        idx += 1;
+        0
+    } else if idx < bytes.len() && matches!(bytes[idx], b'+' | b'-') {
+        let sign = if bytes[idx] == b'-' { -1 } else { 1 };
+        let start = idx;
+        let hours = match digits(s, idx + 1, 2) {
+            Ok(n) => n,
+            Err(err) => return Err(err),
+        };
+        if idx + 3 >= bytes.len() || bytes[idx + 3] != b':' {
+            return Err(TimeError::unexpected(s, idx + 3));
+        }
+        let minutes = match digits(s, idx + 4, 2) {
+            Ok(n) => n,
+            Err(err) => return Err(err),
+        };
+        if hours > 23 || minutes > 59 {
+            return Err(TimeError::new(TimeErrorKind::InvalidOffset, start));
+        }
+        idx += 6;
+        sign * (hours * 3600 + minutes * 60)
+    } else {
+        return Err(TimeError::unexpected(s, idx));
+    };
+    if idx < bytes.len() {
+        return Err(TimeError::unexpected(s, idx));
+    }
+    Ok(days_from_civil(year, month, day) * 86400 + hour * 3600 + minute * 60 + second - offset)
+}
+
+/// Parses exactly `count` ASCII digits starting at `start`.
+const fn digits(s: &str, start: usize, count: usize) -> Result<i64, TimeError> {
+    let bytes = s.as_bytes();
+    let mut idx = start;
+    while idx < start + count {
+        if idx >= bytes.len() || !bytes[idx].is_ascii_digit() {
+            return Err(TimeError::unexpected(s, idx));
+        }
+        idx += 1;
+    }
+    match parse_u64(s.split_at(idx).0.split_at(start).1) {
+        Ok(n) => Ok(n as i64),
+        Err(_) => unreachable!(),
+    }
+}
+
+const fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+const fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Returns the number of days from 1970-01-01 to the date of the proleptic Gregorian calendar.
+const fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    // Years start in March so the leap day is the last day of the year
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month_from_march = (month + 9) % 12;
+    let day_of_year = (153 * month_from_march + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Units from the largest to the smallest, with their names and length in nanoseconds
+const UNITS: [(&[&str], u128); 8] = [
+    (&["w", "week", "weeks"], 604_800_000_000_000),
+    (&["d", "day", "days"], 86_400_000_000_000),
+    (&["h", "hr", "hrs", "hour", "hours"], 3_600_000_000_000),
+    (&["m", "min", "mins", "minute", "minutes"], 60_000_000_000),
+    (&["s", "sec", "secs", "second", "seconds"], 1_000_000_000),
+    (
+        &["ms", "msec", "msecs", "millisecond", "milliseconds"],
+        1_000_000,
+    ),
+    (
+        &["us", "µs", "usec", "usecs", "microsecond", "microseconds"],
+        1_000,
+    ),
+    (&["ns", "nsec", "nsecs", "nanosecond", "nanoseconds"], 1),
+];
+
+/// Parses a duration like `1h30m`.
+/// # Panics: panic if the duration is malformed
+pub const fn parse_duration(s: &str) -> Duration {
+    match try_parse_duration(s) {
+        Ok(duration) => duration,
+        Err(err) => err.panic(),
+    }
+}
+
+/// Parses a duration like `1h30m`, returning an error if it is malformed.
+pub const fn try_parse_duration(s: &str) -> Result<Duration, TimeError> {
+    let bytes = s.as_bytes();
+    let mut nanos: u128 = 0;
+    // Index in UNITS of the next allowed unit
+    let mut next_unit = 0;
+    let mut idx = skip_spaces(s, 0);
+    if idx == bytes.len() {
+        return Err(TimeError::unexpected(s, idx));
+    }
+    while idx < bytes.len() {
+        let number_start = idx;
+        while idx < bytes.len() && bytes[idx].is_ascii_digit() {
+            idx += 1;
+        }
+        if idx == number_start {
+            return Err(TimeError::unexpected(s, idx));
+        }
+        let int = match parse_u64(s.split_at(idx).0.split_at(number_start).1) {
+            Ok(n) => n as u128,
+            Err(_) => return Err(TimeError::new(TimeErrorKind::Overflow, number_start)),
+        };
+        let (mut frac, mut scale): (u128, u128) = (0, 1);
+        if idx < bytes.len() && bytes[idx] == b'.' {
+            idx += 1;
+            let frac_start = idx;
+            while idx < bytes.len() && bytes[idx].is_ascii_digit() {
+                // A whole number of nanoseconds is at most 16 decimals of any unit, so digits
+                // past the 18th can't change the rounded down result
+                if idx - frac_start < 18 {
+                    frac = frac * 10 + (bytes[idx] - b'0') as u128;
+                    scale *= 10;
+                }
+                idx += 1;
+            }
+            if idx == frac_start {
+                return Err(TimeError::unexpected(s, idx));
+            }
+        }
+        idx = skip_spaces(s, idx);
+        let unit_start = idx;
+        while idx < bytes.len() && !bytes[idx].is_ascii_digit() && bytes[idx] != b' ' {
+            idx += 1;
+        }
+        let unit = match find_unit(s.split_at(idx).0.split_at(unit_start).1) {
+            Some(unit) => unit,
+            None if unit_start == idx => return Err(TimeError::unexpected(s, idx)),
+            None => return Err(TimeError::new(TimeErrorKind::UnknownUnit, unit_start)),
+        };
+        if unit < next_unit {
+            return Err(TimeError::new(TimeErrorKind::UnitOrder, unit_start));
+        }
+        next_unit = unit + 1;
+        let length = UNITS[unit].1;
+        nanos += int * length + frac * length / scale;
+        if nanos / 1_000_000_000 > u64::MAX as u128 {
+            return Err(TimeError::new(TimeErrorKind::Overflow, number_start));
+        }
+        idx = skip_spaces(s, idx);
+    }
+    Ok(Duration::new(
+        (nanos / 1_000_000_000) as u64,
+        (nanos % 1_000_000_000) as u32,
+    ))
+}
+
+/// Returns the index in UNITS of the unit with this name.
+const fn find_unit(name: &str) -> Option<usize> {
+    let mut unit = 0;
+    while unit < UNITS.len() {
+        let names = UNITS[unit].0;
+        let mut idx = 0;
+        while idx < names.len() {
+            if crate::kstr::bytes_eq(names[idx].as_bytes(), name.as_bytes()) {
+                return Some(unit);
+            }
+            idx += 1;
+        }
+        unit += 1;
+    }
+    None
+}
+
+/// Formats a duration like `1h 30m 05s`.
+///
+/// Days, hours, minutes and seconds are written from the largest non-zero one, the ones after
+/// the first are padded to two digits, and fractions of seconds are written as decimals.
+/// Durations below a second are written in `ms`, `us` or `ns` instead, like `250ms`.
+/// # Panics: panic if the result doesn't fit in `M`
+pub const fn format_duration<const M: usize>(duration: Duration) -> KStr<M> {
+    let secs = duration.as_secs();
+    let nanos = duration.subsec_nanos();
+    let mut out = KStr::new();
+    if secs == 0 {
+        return if nanos == 0 {
+            out.extend(b"0s")
+        } else if nanos.is_multiple_of(1_000_000) {
+            out.push_u64((nanos / 1_000_000) as u64).extend(b"ms")
+        } else if nanos.is_multiple_of(1_000) {
+            out.push_u64((nanos / 1_000) as u64).extend(b"us")
+        } else {
+            out.push_u64(nanos as u64).extend(b"ns")
+        };
+    }
+    let parts = [
+        (secs / 86400, b'd'),
+        (secs / 3600 % 24, b'h'),
+        (secs / 60 % 60, b'm'),
+        (secs % 60, b's'),
+    ];
+    let mut idx = 0;
+    while parts[idx].0 == 0 {
+        idx += 1;
+    }
+    out = out.push_u64(parts[idx].0);
+    loop {
+        if idx == 3 && nanos != 0 {
+            // Trailing zeros of the fraction are dropped
+            let mut frac = nanos;
+            let mut width = 9;
+            while frac.is_multiple_of(10) {
+                frac /= 10;
+                width -= 1;
+            }
+            out = out.push(b'.');
+            let mut pad = width;
+            while pad > 1 && frac < 10u32.pow(pad - 1) {
+                out = out.push(b'0');
+                pad -= 1;
+            }
+            out = out.push_u64(frac as u64);
+        }
+        out = out.push(parts[idx].1);
+        idx += 1;
+        if idx == 4 {
+            return out;
+        }
+        out = out.push(b' ');
+        if parts[idx].0 < 10 {
+            out = out.push(b'0');
+        }
+        out = out.push_u64(parts[idx].0);
+    }
+}
+
+#[cfg(test)]
+mod time_test {
+    use super::*;
+
+    const EPOCH: i64 = parse_rfc3339("1970-01-01T00:00:00Z");
+    const _: () = {
+        assert!(EPOCH == 0);
+        assert!(parse_duration("2m").as_secs() == 120);
+        assert!(format_duration::<8>(Duration::from_millis(1500)).len() == 4);
+    };
+
+    #[test]
+    fn timestamps() {
+        for (s, secs) in [
+            ("2026-10-18T12:00:00Z", 1_792_324_800),
+            ("2026-10-18t14:00:00.123456789+02:00", 1_792_324_800),
+            ("2026-10-18 07:30:00-04:30", 1_792_324_800),
+            ("1969-12-31T23:59:59Z", -1),
+            ("2000-02-29T00:00:00Z", 951_782_400),
+            ("1900-03-01T00:00:00Z", -2_203_891_200),
+            ("0000-01-01T00:00:00Z", -62_167_219_200),
+            ("9999-12-31T23:59:59Z", 253_402_300_799),
+            ("2016-12-31T23:59:60Z", 1_483_228_800),
+            ("2038-01-19T03:14:08z", 2_147_483_648),
+        ] {
+            assert_eq!(try_parse_rfc3339(s), Ok(secs), "{s}");
+        }
+    }
+
+    #[test]
+    fn timestamp_errors() {
+        for (s, kind, offset) in [
+            ("", TimeErrorKind::UnexpectedEnd, 0),
+            ("2026-10-18", TimeErrorKind::UnexpectedEnd, 10),
+            ("2026-10-18T12:00:00", TimeErrorKind::UnexpectedEnd, 19),
+            ("2026/10/18T12:00:00Z", TimeErrorKind::UnexpectedChar, 4),
+            ("26-10-18T12:00:00Z", TimeErrorKind::UnexpectedChar, 2),
+            ("2026-13-01T00:00:00Z", TimeErrorKind::InvalidMonth, 5),
+            ("2026-00-01T00:00:00Z", TimeErrorKind::InvalidMonth, 5),
+            ("2026-02-29T00:00:00Z", TimeErrorKind::InvalidDay, 8),
+            ("1900-02-29T00:00:00Z", TimeErrorKind::InvalidDay, 8),
+            ("2026-04-31T00:00:00Z", TimeErrorKind::InvalidDay, 8),
+            ("2026-10-18T24:00:00Z", TimeErrorKind::InvalidTime, 11),
+            ("2026-10-18T12:60:00Z", TimeErrorKind::InvalidTime, 14),
+            ("2026-10-18T12:00:61Z", TimeErrorKind::InvalidTime, 17),
+            ("2026-10-18T12:00:00.Z", TimeErrorKind::UnexpectedChar, 20),
+            (
+                "2026-10-18T12:00:00+24:00",
+                TimeErrorKind::InvalidOffset,
+                19,
+            ),
+            (
+                "2026-10-18T12:00:00+0200",
+                TimeErrorKind::UnexpectedChar,
+                22,
+            ),
+            ("2026-10-18T12:00:00Z ", TimeErrorKind::UnexpectedChar, 20),
+        ] {
+            assert_eq!(
+                try_parse_rfc3339(s),
+                Err(TimeError::new(kind, offset)),
+                "{s:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn durations() {
+        for (s, duration) in [
+            ("1h30m", Duration::from_secs(5400)),
+            ("1h 30m 05s", Duration::from_secs(5405)),
+            ("2 days 4 hours", Duration::from_secs(187_200)),
+            ("1w", Duration::from_secs(604_800)),
+            ("250ms", Duration::from_millis(250)),
+            ("1s 500us 7ns", Duration::new(1, 500_007)),
+            ("3µs", Duration::from_micros(3)),
+            ("1.5h", Duration::from_secs(5400)),
+            ("0.000000001s", Duration::from_nanos(1)),
+            ("0.0000000019s", Duration::from_nanos(1)),
+            ("0.0000000000000019w", Duration::from_nanos(1)),
+            ("0.0000000000000116d", Duration::from_nanos(1)),
+            ("0.00000000000000000000001w", Duration::ZERO),
+            (" 0s ", Duration::ZERO),
+            ("18446744073709551615s", Duration::from_secs(u64::MAX)),
+        ] {
+            assert_eq!(try_parse_duration(s), Ok(duration), "{s}");
+        }
+    }
+
+    #[test]
+    fn duration_errors() {
+        for (s, kind, offset) in [
+            ("", TimeErrorKind::UnexpectedEnd, 0),
+            ("10", TimeErrorKind::UnexpectedEnd, 2),
+            ("h", TimeErrorKind::UnexpectedChar, 0),
+            ("1.h", TimeErrorKind::UnexpectedChar, 2),
+            ("5 parsecs", TimeErrorKind::UnknownUnit, 2),
+            ("1m 1h", TimeErrorKind::UnitOrder, 4),
+            ("1s 2s", TimeErrorKind::UnitOrder, 4),
+            ("18446744073709551616s", TimeErrorKind::Overflow, 0),
+            ("18446744073709551615s 1000ms", TimeErrorKind::Overflow, 22),
+        ] {
+            assert_eq!(
+                try_parse_duration(s),
+                Err(TimeError::new(kind, offset)),
+                "{s:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn format() {
+        for (duration, s) in [
+            (Duration::ZERO, "0s"),
+            (Duration::from_secs(5), "5s"),
+            (Duration::from_secs(5405), "1h 30m 05s"),
+            (Duration::from_secs(3600), "1h 00m 00s"),
+            (Duration::from_secs(90061), "1d 01h 01m 01s"),
+            (Duration::from_millis(250), "250ms"),
+            (Duration::from_micros(1500), "1500us"),
+            (Duration::from_nanos(7), "7ns"),
+            (Duration::from_millis(65_250), "1m 05.25s"),
+            (Duration::new(1, 5), "1.000000005s"),
+        ] {
+            assert_eq!(format_duration::<32>(duration).as_str(), s);
+            assert_eq!(try_parse_duration(s), Ok(duration), "{s}");
+        }
+    }
+}
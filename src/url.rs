@@ -0,0 +1,546 @@
+//! URLs follow the RFC 3986 URI syntax: `scheme:[//[userinfo@]host[:port]]path[?query][#fragment]`.
+//! Only ASCII is allowed, other chars must be percent-encoded. Hosts are registered names like
+//! `example.com` or IP literals like `[::1]`, and a port must fit in a `u16`.
+//!
+//! Parts are returned as written, use [`percent_decode`](crate::url::percent_decode) to decode them.
+//!
+//! ```rust
+//! use konster::url::{percent_decode, KUrl};
+//!
+//! const API: KUrl<64> = KUrl::parse("https://bot@api.example.com:8443/v1/a%20b?q=1#top");
+//! const PORT: u16 = match API.port() {
+//!     Some(port) => port,
+//!     None => 443,
+//! };
+//! assert_eq!(PORT, 8443);
+//! assert_eq!(API.scheme(), "https");
+//! assert_eq!(API.host(), Some("api.example.com"));
+//! assert_eq!(API.query(), Some("q=1"));
+//! assert_eq!(percent_decode::<16>(API.path()).unwrap().as_str(), "/v1/a b");
+//! ```
+use crate::error::impl_error;
+use crate::kstr::{char_at, hex_digit, KStr, HEX_UPPER};
+use crate::kvec::KVec;
+
+/// Error returned when parsing a malformed URL or decoding malformed percent-encoding
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UrlError {
+    /// What is wrong with the URL
+    pub kind: UrlErrorKind,
+    /// Byte index in the input where the error was found
+    pub offset: usize,
+}
+
+/// The different ways a URL can be malformed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UrlErrorKind {
+    /// The URL doesn't start with a scheme followed by `:`
+    MissingScheme,
+    /// A char that is not allowed in this part of the URL
+    InvalidChar(char),
+    /// A `%` not followed by two hex digits
+    InvalidPercentEncoding,
+    /// An IP literal without its closing `]` or with chars other than hex digits, `:` and `.`
+    InvalidHost,
+    /// A port that is not a number or doesn't fit in a `u16`
+    InvalidPort,
+    /// Percent-encoded bytes that are not valid UTF-8
+    InvalidUtf8,
+}
+
+impl UrlErrorKind {
+    /// Returns a description of the error.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            UrlErrorKind::MissingScheme => "missing scheme",
+            UrlErrorKind::InvalidChar(_) => "invalid char",
+            UrlErrorKind::InvalidPercentEncoding => "invalid percent-encoding",
+            UrlErrorKind::InvalidHost => "invalid host",
+            UrlErrorKind::InvalidPort => "invalid port",
+            UrlErrorKind::InvalidUtf8 => "invalid UTF-8",
+        }
+    }
+}
+
+impl UrlError {
+    const fn new(kind: UrlErrorKind, offset: usize) -> Self {
+        Self { kind, offset }
+    }
+    /// Error for the char containing the byte index.
+    const fn invalid_char(s: &str, mut idx: usize) -> Self {
+        while !s.is_char_boundary(idx) {
+            idx -= 1;
+        }
+        match char_at(s, idx) {
+            Some((c, _)) => Self::new(UrlErrorKind::InvalidChar(c), idx),
+            None => unreachable!(),
+        }
+    }
+}
+impl_error!(UrlError char UrlErrorKind::InvalidChar, " at byte " offset);
+
+/// Byte range of a part of the URL
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Span {
+    start: usize,
+    end: usize,
+}
+
+/// A validated URL stored in a String of capacity `N`, with the spans of its parts
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KUrl<const N: usize> {
+    url: KStr<N>,
+    scheme: Span,
+    userinfo: Option<Span>,
+    host: Option<Span>,
+    port: Option<u16>,
+    path: Span,
+    query: Option<Span>,
+    fragment: Option<Span>,
+}
+
+impl<const N: usize> KUrl<N> {
+    /// Parses a URL.
+    /// # Panics: panic if the URL is malformed or doesn't fit in `N`
+    pub const fn parse(s: &str) -> Self {
+        match Self::try_parse(s) {
+            Ok(url) => url,
+            Err(err) => err.panic(),
+        }
+    }
+    /// Parses a URL, or returns where it is malformed.
+    /// # Panics: panic if the URL doesn't fit in `N`
+    pub const fn try_parse(s: &str) -> Result<Self, UrlError> {
+        let bytes = s.as_bytes();
+        let mut idx = 0;
+        while idx < bytes.len()
+            && (bytes[idx].is_ascii_alphanumeric() || matches!(bytes[idx], b'+' | b'-' | b'.'))
+        {
+            idx += 1;
+        }
+        if idx == bytes.len() || idx == 0 || matches!(bytes[idx], b'/' | b'?' | b'#') {
+            return Err(UrlError::new(UrlErrorKind::MissingScheme, 0));
+        }
+        if bytes[idx] != b':' {
+            return Err(UrlError::invalid_char(s, idx));
+        }
+        if !bytes[0].is_ascii_alphabetic() {
+            return Err(UrlError::invalid_char(s, 0));
+        }
+        let mut url = Self {
+            url: KStr::from_str(s),
+            scheme: Span { start: 0, end: idx },
+            userinfo: None,
+            host: None,
+            port: None,
+            path: Span { start: 0, end: 0 },
+            query: None,
+            fragment: None,
+        };
+        idx += 1;
+        if idx + 1 < bytes.len() && bytes[idx] == b'/' && bytes[idx + 1] == b'/' {
+            idx = match url.authority(s, idx + 2) {
+                Ok(end) => end,
+                Err(err) => return Err(err),
+            };
+        }
+        let end = find(s, idx, b"?#");
+        url.path = match check(s, idx, end, b":@/") {
+            Ok(span) => span,
+            Err(err) => return Err(err),
+        };
+        idx = end;
+        if idx < bytes.len() && bytes[idx] == b'?' {
+            let end = find(s, idx + 1, b"#");
+            url.query = match check(s, idx + 1, end, b":@/?") {
+                Ok(span) => Some(span),
+                Err(err) => return Err(err),
+            };
+            idx = end;
+        }
+        if idx < bytes.len() {
+            url.fragment = match check(s, idx + 1, bytes.len(), b":@/?") {
+                Ok(span) => Some(span),
+                Err(err) => return Err(err),
+            };
+        }
+        Ok(url)
+    }
+
+    /// Parses the userinfo, host and port starting at `start`, returns the index after them.
+    const fn authority(&mut self, s: &str, start: usize) -> Result<usize, UrlError> {
+        let bytes = s.as_bytes();
+        let end = find(s, start, b"/?#");
+        let mut idx = start;
+        let at = find(s, start, b"@");
+        if at < end {
+            self.userinfo = match check(s, start, at, b":") {
+                Ok(span) => Some(span),
+                Err(err) => return Err(err),
+            };
+            idx = at + 1;
+        }
+        if idx < end && bytes[idx] == b'[' {
+            let close = find(s, idx, b"]");
+            if close >= end || close == idx + 1 {
+                return Err(UrlError::new(UrlErrorKind::InvalidHost, idx));
+            }
+            let mut c = idx + 1;
+            while c < close {
+                if !(bytes[c].is_ascii_hexdigit() || bytes[c] == b':' || bytes[c] == b'.') {
+                    return Err(UrlError::new(UrlErrorKind::InvalidHost, idx));
+                }
+                c += 1;
+            }
+            self.host = Some(Span {
+                start: idx,
+                end: close + 1,
+            });
+            idx = close + 1;
+            if idx < end && bytes[idx] != b':' {
+                return Err(UrlError::invalid_char(s, idx));
+            }
+        } else {
+            let host_end = find(s, idx, b":");
+            let host_end = if host_end < end { host_end } else { end };
+            self.host = match check(s, idx, host_end, b"") {
+                Ok(span) => Some(span),
+                Err(err) => return Err(err),
+            };
+            idx = host_end;
+        }
+        if idx < end {
+            // An empty port is allowed and means the default one
+            let mut port: u32 = 0;
+            let mut c = idx + 1;
+            while c < end {
+                if !bytes[c].is_ascii_digit() {
+                    return Err(UrlError::new(UrlErrorKind::InvalidPort, c));
+                }
+                port = port * 10 + (bytes[c] - b'0') as u32;
+                if port > u16::MAX as u32 {
+                    return Err(UrlError::new(UrlErrorKind::InvalidPort, idx + 1));
+                }
+                c += 1;
+            }
+            if c > idx + 1 {
+                self.port = Some(port as u16);
+            }
+        }
+        Ok(end)
+    }
+
+    /// Returns the URL
+    pub const fn as_str(&self) -> &str {
+        self.url.as_str()
+    }
+
+    /// Returns the scheme, like `https`
+    pub const fn scheme(&self) -> &str {
+        self.span(self.scheme)
+    }
+
+    /// Returns the userinfo before the `@`, like `user:password`
+    pub const fn userinfo(&self) -> Option<&str> {
+        match self.userinfo {
+            Some(span) => Some(self.span(span)),
+            None => None,
+        }
+    }
+
+    /// Returns the host, with the brackets of IP literals, or None if the URL has no authority
+    pub const fn host(&self) -> Option<&str> {
+        match self.host {
+            Some(span) => Some(self.span(span)),
+            None => None,
+        }
+    }
+
+    /// Returns the port, or None if the URL doesn't give one
+    pub const fn port(&self) -> Option<u16> {
+        self.port
+    }
+
+    /// Returns the path, which may be empty
+    pub const fn path(&self) -> &str {
+        self.span(self.path)
+    }
+
+    /// Returns the query without the `?`
+    pub const fn query(&self) -> Option<&str> {
+        match self.query {
+            Some(span) => Some(self.span(span)),
+            None => None,
+        }
+    }
+
+    /// Returns the fragment without the `#`
+    pub const fn fragment(&self) -> Option<&str> {
+        match self.fragment {
+            Some(span) => Some(self.span(span)),
+            None => None,
+        }
+    }
+
+    const fn span(&self, span: Span) -> &str {
+        self.url
+            .as_str()
+            .split_at(span.end)
+            .0
+            .split_at(span.start)
+            .1
+    }
+}
+
+/// Returns the index of the first of the `bytes` after `start`, or the length of the String.
+const fn find(s: &str, start: usize, bytes: &[u8]) -> usize {
+    let s = s.as_bytes();
+    let mut idx = start;
+    while idx < s.len() {
+        let mut b = 0;
+        while b < bytes.len() {
+            if s[idx] == bytes[b] {
+                return idx;
+            }
+            b += 1;
+        }
+        idx += 1;
+    }
+    s.len()
+}
+
+/// Checks that `start..end` only has unreserved chars, sub-delims, percent-encoded bytes and the `extra` chars.
+const fn check(s: &str, start: usize, end: usize, extra: &[u8]) -> Result<Span, UrlError> {
+    let bytes = s.as_bytes();
+    let mut idx = start;
+    'chars: while idx < end {
+        match bytes[idx] {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {}
+            b'!' | b'$' | b'&' | b'\'' | b'(' | b')' | b'*' | b'+' | b',' | b';' | b'=' => {}
+            b'%' => {
+                if idx + 2 >= end
+                    || !bytes[idx + 1].is_ascii_hexdigit()
+                    || !bytes[idx + 2].is_ascii_hexdigit()
+                {
+                    return Err(UrlError::new(UrlErrorKind::InvalidPercentEncoding, idx));
+                }
+                idx += 3;
+                continue;
+            }
+            b => {
+                let mut e = 0;
+                while e < extra.len() {
+                    if b == extra[e] {
+                        idx += 1;
+                        continue 'chars;
+                    }
+                    e += 1;
+                }
+                return Err(UrlError::invalid_char(s, idx));
+            }
+        }
+        idx += 1;
+    }
+    Ok(Span { start, end })
+}
+
+/// Decodes the `%XX` sequences of the String, other chars are kept as is.
+/// # Panics: panic if the result doesn't fit in `M`
+pub const fn percent_decode<const M: usize>(s: &str) -> Result<KStr<M>, UrlError> {
+    let bytes = s.as_bytes();
+    let mut out = KVec::<u8, M>::new(0);
+    let mut idx = 0;
+    while idx < bytes.len() {
+        if bytes[idx] != b'%' {
+            out = out.push(bytes[idx]);
+            idx += 1;
+            continue;
+        }
+        let decoded = if idx + 2 < bytes.len() {
+            match (hex_digit(bytes[idx + 1]), hex_digit(bytes[idx + 2])) {
+                (Some(high), Some(low)) => high << 4 | low,
+                _ => return Err(UrlError::new(UrlErrorKind::InvalidPercentEncoding, idx)),
+            }
+        } else {
+            return Err(UrlError::new(UrlErrorKind::InvalidPercentEncoding, idx));
+        };
+        out = out.push(decoded);
+        idx += 3;
+    }
+    match std::str::from_utf8(out.as_slice()) {
+        Ok(decoded) => Ok(KStr::from_str(decoded)),
+        Err(err) => {
+            // Find the input byte that produced the first invalid output byte
+            let mut decoded = 0;
+            let mut idx = 0;
+            while decoded < err.valid_up_to() {
+                idx += if bytes[idx] == b'%' { 3 } else { 1 };
+                decoded += 1;
+            }
+            Err(UrlError::new(UrlErrorKind::InvalidUtf8, idx))
+        }
+    }
+}
+
+/// Encodes every byte of the String except ASCII alphanumerics, `-`, `.`, `_` and `~` as `%XX`.
+/// # Panics: panic if the result doesn't fit in `M`
+pub const fn percent_encode<const M: usize>(s: &str) -> KStr<M> {
+    let bytes = s.as_bytes();
+    let mut out = KStr::new();
+    let mut idx = 0;
+    while idx < bytes.len() {
+        out = match bytes[idx] {
+            b @ (b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~') => {
+                out.push(b)
+            }
+            b => out
+                .push(b'%')
+                .push(HEX_UPPER[(b >> 4) as usize])
+                .push(HEX_UPPER[(b & 0xf) as usize]),
+        };
+        idx += 1;
+    }
+    out
+}
+
+#[cfg(test)]
+mod url_test {
+    use super::*;
+
+    const URL: KUrl<64> = KUrl::parse("postgres://db.local:5432/app");
+    const _: () = {
+        assert!(matches!(URL.port(), Some(5432)));
+        assert!(URL.path().len() == 4);
+        assert!(percent_encode::<8>("a b").len() == 5);
+    };
+
+    type Parts<'a> = (
+        &'a str,
+        Option<&'a str>,
+        Option<&'a str>,
+        Option<u16>,
+        &'a str,
+        Option<&'a str>,
+        Option<&'a str>,
+    );
+
+    fn parts(url: &KUrl<64>) -> Parts<'_> {
+        (
+            url.scheme(),
+            url.userinfo(),
+            url.host(),
+            url.port(),
+            url.path(),
+            url.query(),
+            url.fragment(),
+        )
+    }
+
+    #[test]
+    fn parse() {
+        for (s, expected) in [
+            (
+                "https://u:p@example.com:8080/a/b?x=1&y=2#frag",
+                (
+                    "https",
+                    Some("u:p"),
+                    Some("example.com"),
+                    Some(8080),
+                    "/a/b",
+                    Some("x=1&y=2"),
+                    Some("frag"),
+                ),
+            ),
+            (
+                "http://[::1]:80",
+                ("http", None, Some("[::1]"), Some(80), "", None, None),
+            ),
+            (
+                "file:///etc/hosts",
+                ("file", None, Some(""), None, "/etc/hosts", None, None),
+            ),
+            (
+                "mailto:a@b.c",
+                ("mailto", None, None, None, "a@b.c", None, None),
+            ),
+            (
+                "urn:isbn:0451450523",
+                ("urn", None, None, None, "isbn:0451450523", None, None),
+            ),
+            (
+                "s3+v2://h:/?#",
+                ("s3+v2", None, Some("h"), None, "/", Some(""), Some("")),
+            ),
+            (
+                "x:?q=/?#f/?",
+                ("x", None, None, None, "", Some("q=/?"), Some("f/?")),
+            ),
+        ] {
+            let url = KUrl::<64>::parse(s);
+            assert_eq!(parts(&url), expected, "{s}");
+            assert_eq!(url.as_str(), s);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        for (s, kind, offset) in [
+            ("", UrlErrorKind::MissingScheme, 0),
+            ("example.com", UrlErrorKind::MissingScheme, 0),
+            ("//example.com", UrlErrorKind::MissingScheme, 0),
+            ("/a:b", UrlErrorKind::MissingScheme, 0),
+            ("1http://a", UrlErrorKind::InvalidChar('1'), 0),
+            ("ht_tp://a", UrlErrorKind::InvalidChar('_'), 2),
+            ("http://exa mple.com", UrlErrorKind::InvalidChar(' '), 10),
+            ("http://a/é", UrlErrorKind::InvalidChar('é'), 9),
+            ("http://a/%2", UrlErrorKind::InvalidPercentEncoding, 9),
+            ("http://a/%zz", UrlErrorKind::InvalidPercentEncoding, 9),
+            ("http://a:65536/", UrlErrorKind::InvalidPort, 9),
+            ("http://a:8o/", UrlErrorKind::InvalidPort, 10),
+            ("http://[::1/", UrlErrorKind::InvalidHost, 7),
+            ("http://[]/", UrlErrorKind::InvalidHost, 7),
+            ("http://[g::]/", UrlErrorKind::InvalidHost, 7),
+            ("http://[::1]x/", UrlErrorKind::InvalidChar('x'), 12),
+            ("http://a@b@c/", UrlErrorKind::InvalidChar('@'), 10),
+            ("http://a/#b#", UrlErrorKind::InvalidChar('#'), 11),
+        ] {
+            assert_eq!(
+                KUrl::<64>::try_parse(s),
+                Err(UrlError::new(kind, offset)),
+                "{s:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn percent() {
+        assert_eq!(percent_decode::<16>("a%20b%2Fc").unwrap().as_str(), "a b/c");
+        assert_eq!(
+            percent_decode::<16>("%C3%A9t%c3%a9").unwrap().as_str(),
+            "été"
+        );
+        assert_eq!(
+            percent_decode::<16>("ab%4"),
+            Err(UrlError::new(UrlErrorKind::InvalidPercentEncoding, 2))
+        );
+        assert_eq!(
+            percent_decode::<16>("ab%C3%28"),
+            Err(UrlError::new(UrlErrorKind::InvalidUtf8, 2))
+        );
+        assert_eq!(percent_encode::<32>("a b/é~").as_str(), "a%20b%2F%C3%A9~");
+        assert_eq!(
+            UrlError::new(UrlErrorKind::InvalidChar(' '), 3).to_string(),
+            "invalid char ' ' at byte 3"
+        );
+        assert_eq!(
+            UrlError::new(UrlErrorKind::InvalidChar('\n'), 3).to_string(),
+            "invalid char '\\n' at byte 3"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_long() {
+        let _ = KUrl::<8>::try_parse("https://example.com");
+    }
+}
@@ -0,0 +1,255 @@
+//! UUIDs are parsed from their hyphenated `67e55044-10b1-426f-9247-bb680e5fe0c8`, simple
+//! `67e5504410b1426f9247bb680e5fe0c8`, braced `{67e55044-10b1-426f-9247-bb680e5fe0c8}` and URN
+//! `urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8` forms, with hex digits of either case.
+//!
+//! ```rust
+//! use konster::uuid::{self, Variant};
+//!
+//! const ID: [u8; 16] = uuid::parse("67e55044-10b1-426f-9247-bb680e5fe0c8");
+//! const _: () = assert!(uuid::version(&ID) == 4);
+//! assert_eq!(uuid::variant(&ID), Variant::Rfc4122);
+//! assert_eq!(uuid::format(&ID).as_str(), "67e55044-10b1-426f-9247-bb680e5fe0c8");
+//! ```
+use crate::encoding::{hex_value, invalid_char, DecodeError, DecodeErrorKind};
+use crate::kstr::{bytes_eq, KStr, HEX_LOWER, HEX_UPPER};
+
+/// The nil UUID, with all bits set to zero
+pub const NIL: [u8; 16] = [0; 16];
+
+/// The layout of a UUID, given by the high bits of its 9th byte
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Variant {
+    /// Reserved for backward compatibility with NCS UUIDs
+    Ncs,
+    /// The layout of RFC 4122 and RFC 9562, used by every version
+    Rfc4122,
+    /// Reserved for backward compatibility with Microsoft GUIDs
+    Microsoft,
+    /// Reserved for future definition
+    Future,
+}
+
+/// Parses a UUID in any of its forms.
+/// # Panics: panic if the UUID is malformed
+pub const fn parse(s: &str) -> [u8; 16] {
+    match try_parse(s) {
+        Ok(uuid) => uuid,
+        Err(err) => err.panic(),
+    }
+}
+
+/// Parses a UUID in any of its forms, returning an error if it is malformed.
+///
+/// A String whose length matches none of the forms gives [`DecodeErrorKind::InvalidLength`], with
+/// the length as offset.
+pub const fn try_parse(s: &str) -> Result<[u8; 16], DecodeError> {
+    let bytes = s.as_bytes();
+    let (start, hyphenated) = match bytes.len() {
+        32 => (0, false),
+        36 => (0, true),
+        38 => {
+            if bytes[0] != b'{' {
+                return Err(invalid_char(s, 0));
+            }
+            if bytes[37] != b'}' {
+                return Err(invalid_char(s, 37));
+            }
+            (1, true)
+        }
+        45 => {
+            let prefix = b"urn:uuid:";
+            if !bytes_eq(bytes.split_at(prefix.len()).0, prefix) {
+                let mut idx = 0;
+                while bytes[idx] == prefix[idx] {
+                    idx += 1;
+                }
+                return Err(invalid_char(s, idx));
+            }
+            (prefix.len(), true)
+        }
+        len => return Err(DecodeError::new(DecodeErrorKind::InvalidLength, len)),
+    };
+    let mut uuid = [0; 16];
+    let mut idx = start;
+    let mut byte = 0;
+    while byte < 16 {
+        if hyphenated && matches!(byte, 4 | 6 | 8 | 10) {
+            if bytes[idx] != b'-' {
+                return Err(invalid_char(s, idx));
+            }
+            idx += 1;
+        }
+        let high = match hex_value(s, idx) {
+            Ok(v) => v,
+            Err(err) => return Err(err),
+        };
+        let low = match hex_value(s, idx + 1) {
+            Ok(v) => v,
+            Err(err) => return Err(err),
+        };
+        uuid[byte] = high << 4 | low;
+        idx += 2;
+        byte += 1;
+    }
+    Ok(uuid)
+}
+
+/// Returns the version, the high nibble of the 7th byte, like 4 for random UUIDs
+pub const fn version(uuid: &[u8; 16]) -> u8 {
+    uuid[6] >> 4
+}
+
+/// Returns the variant
+pub const fn variant(uuid: &[u8; 16]) -> Variant {
+    match uuid[8] {
+        0x00..=0x7f => Variant::Ncs,
+        0x80..=0xbf => Variant::Rfc4122,
+        0xc0..=0xdf => Variant::Microsoft,
+        _ => Variant::Future,
+    }
+}
+
+/// Formats the UUID in its lowercase hyphenated form.
+pub const fn format(uuid: &[u8; 16]) -> KStr<36> {
+    encode(uuid, HEX_LOWER, true)
+}
+
+/// Formats the UUID in its uppercase hyphenated form.
+pub const fn format_upper(uuid: &[u8; 16]) -> KStr<36> {
+    encode(uuid, HEX_UPPER, true)
+}
+
+/// Formats the UUID in its lowercase simple form, without hyphens.
+pub const fn format_simple(uuid: &[u8; 16]) -> KStr<32> {
+    encode(uuid, HEX_LOWER, false)
+}
+
+const fn encode<const M: usize>(uuid: &[u8; 16], digits: &[u8; 16], hyphenated: bool) -> KStr<M> {
+    let mut out = KStr::new();
+    let mut idx = 0;
+    while idx < 16 {
+        if hyphenated && matches!(idx, 4 | 6 | 8 | 10) {
+            out = out.push(b'-');
+        }
+        out = out
+            .push(digits[(uuid[idx] >> 4) as usize])
+            .push(digits[(uuid[idx] & 0xf) as usize]);
+        idx += 1;
+    }
+    out
+}
+
+#[cfg(test)]
+mod uuid_test {
+    use super::*;
+
+    const ID: [u8; 16] = parse("{6BA7B810-9DAD-11D1-80B4-00C04FD430C8}");
+    const _: () = {
+        assert!(version(&ID) == 1);
+        assert!(ID[15] == 0xc8);
+        assert!(format_simple(&NIL).len() == 32);
+    };
+
+    const HYPHENATED: &str = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+
+    #[test]
+    fn forms() {
+        let expected = [
+            0x67, 0xe5, 0x50, 0x44, 0x10, 0xb1, 0x42, 0x6f, 0x92, 0x47, 0xbb, 0x68, 0x0e, 0x5f,
+            0xe0, 0xc8,
+        ];
+        for s in [
+            HYPHENATED,
+            "67E55044-10B1-426F-9247-BB680E5FE0C8",
+            "67e5504410b1426f9247bb680e5fe0c8",
+            "{67e55044-10b1-426f-9247-bb680e5fe0c8}",
+            "urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8",
+        ] {
+            assert_eq!(try_parse(s), Ok(expected), "{s}");
+        }
+        assert_eq!(format(&expected).as_str(), HYPHENATED);
+        assert_eq!(
+            format_upper(&expected).as_str(),
+            "67E55044-10B1-426F-9247-BB680E5FE0C8"
+        );
+        assert_eq!(
+            format_simple(&expected).as_str(),
+            "67e5504410b1426f9247bb680e5fe0c8"
+        );
+    }
+
+    #[test]
+    fn errors() {
+        for (s, kind, offset) in [
+            ("", DecodeErrorKind::InvalidLength, 0),
+            (
+                "67e55044-10b1-426f-9247-bb680e5fe0c",
+                DecodeErrorKind::InvalidLength,
+                35,
+            ),
+            (
+                "67e55044-10b1-426f-9247-bb680e5fe0cg",
+                DecodeErrorKind::InvalidChar('g'),
+                35,
+            ),
+            (
+                "67e55044_10b1-426f-9247-bb680e5fe0c8",
+                DecodeErrorKind::InvalidChar('_'),
+                8,
+            ),
+            (
+                "67e550441-0b1-426f-9247-bb680e5fe0c8",
+                DecodeErrorKind::InvalidChar('1'),
+                8,
+            ),
+            (
+                "67e5504-410b1-426f-9247-bb680e5fe0c8",
+                DecodeErrorKind::InvalidChar('-'),
+                7,
+            ),
+            (
+                "67e55044-10b1-426f-9247-bb680e5fe0é",
+                DecodeErrorKind::InvalidChar('é'),
+                34,
+            ),
+            (
+                "(67e55044-10b1-426f-9247-bb680e5fe0c8}",
+                DecodeErrorKind::InvalidChar('('),
+                0,
+            ),
+            (
+                "{67e55044-10b1-426f-9247-bb680e5fe0c8)",
+                DecodeErrorKind::InvalidChar(')'),
+                37,
+            ),
+            (
+                "urn:uid:067e55044-10b1-426f-9247-bb680e5fe0c8",
+                DecodeErrorKind::InvalidChar('i'),
+                5,
+            ),
+        ] {
+            assert_eq!(try_parse(s), Err(DecodeError::new(kind, offset)), "{s:?}");
+        }
+    }
+
+    #[test]
+    fn versions_and_variants() {
+        assert_eq!(version(&parse(HYPHENATED)), 4);
+        assert_eq!(version(&parse("018f2a4e-7c1b-7d3e-8a5f-0123456789ab")), 7);
+        assert_eq!(variant(&NIL), Variant::Ncs);
+        assert_eq!(
+            variant(&parse("00000000-0000-0000-c000-000000000000")),
+            Variant::Microsoft
+        );
+        assert_eq!(
+            variant(&parse("ffffffff-ffff-ffff-ffff-ffffffffffff")),
+            Variant::Future
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn malformed() {
+        let _ = parse("not a uuid");
+    }
+}
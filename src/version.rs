@@ -0,0 +1,682 @@
+//! Versions follow SemVer 2.0: `major.minor.patch`, an optional pre-release like `-beta.2` and
+//! optional build metadata like `+sha.5114f85`. Build metadata is kept but ignored when comparing.
+//!
+//! Requirements use Cargo's syntax: comma separated comparators made of an operator among `^`,
+//! `~`, `=`, `>`, `>=`, `<` and `<=` (no operator means `^`) and a version that can omit its
+//! minor and patch numbers, like `>=1.2, <1.5` or `~1.4`. A pre-release version only matches if
+//! a comparator has a pre-release with the same `major.minor.patch`.
+//!
+//! ```rust
+//! use konster::version::KVersion;
+//!
+//! const MIN_PROTOCOL: KVersion = KVersion::parse("1.4.0-beta.2");
+//! const CURRENT: KVersion = KVersion::parse("1.4.0");
+//! const _: () = assert!(CURRENT.cmp(&MIN_PROTOCOL).is_gt());
+//! const _: () = assert!(CURRENT.matches("^1.3"));
+//! assert!(!MIN_PROTOCOL.matches(">=1.4.0"));
+//! assert_eq!(MIN_PROTOCOL.to_kstr::<16>().as_str(), "1.4.0-beta.2");
+//! ```
+use crate::error::impl_error;
+use crate::kstr::{bytes_eq, parse_u64, skip_spaces, KStr};
+use std::cmp::Ordering;
+
+/// Error returned when parsing a malformed version or requirement
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VersionError {
+    /// What is wrong with the version
+    pub kind: VersionErrorKind,
+    /// Byte index in the version or requirement where the error was found
+    pub offset: usize,
+}
+
+/// The different ways a version or requirement can be malformed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VersionErrorKind {
+    /// A char that is not allowed here
+    UnexpectedChar,
+    /// The version or requirement ends too early
+    UnexpectedEnd,
+    /// A number or a numeric pre-release identifier starting with `0`
+    LeadingZero,
+    /// A number that doesn't fit in a `u64`
+    Overflow,
+    /// An empty pre-release or build identifier, like in `1.0.0-a..b`
+    EmptyIdentifier,
+}
+
+impl VersionErrorKind {
+    /// Returns a description of the error.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            VersionErrorKind::UnexpectedChar => "unexpected char",
+            VersionErrorKind::UnexpectedEnd => "unexpected end",
+            VersionErrorKind::LeadingZero => "number with a leading zero",
+            VersionErrorKind::Overflow => "number out of range",
+            VersionErrorKind::EmptyIdentifier => "empty identifier",
+        }
+    }
+}
+
+impl VersionError {
+    const fn new(kind: VersionErrorKind, offset: usize) -> Self {
+        Self { kind, offset }
+    }
+    /// Builds the error for an unexpected char or end at `offset`.
+    const fn unexpected(s: &str, offset: usize) -> Self {
+        if offset < s.len() {
+            Self::new(VersionErrorKind::UnexpectedChar, offset)
+        } else {
+            Self::new(VersionErrorKind::UnexpectedEnd, offset)
+        }
+    }
+}
+impl_error!(VersionError, " at byte " offset);
+
+/// A semantic version whose pre-release and build metadata fit in `N` bytes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KVersion<const N: usize = 32> {
+    /// Major version, for incompatible changes
+    pub major: u64,
+    /// Minor version, for compatible additions
+    pub minor: u64,
+    /// Patch version, for compatible fixes
+    pub patch: u64,
+    /// Pre-release identifiers without the `-`, empty for a release
+    pub pre: KStr<N>,
+    /// Build metadata without the `+`
+    pub build: KStr<N>,
+}
+
+impl<const N: usize> KVersion<N> {
+    /// Parses a version.
+    /// # Panics: panic if the version is malformed, or its pre-release or build metadata doesn't fit in `N`
+    pub const fn parse(s: &str) -> Self {
+        match Self::try_parse(s) {
+            Ok(version) => version,
+            Err(err) => err.panic(),
+        }
+    }
+
+    /// Parses a version, returning an error if it is malformed.
+    /// # Panics: panic if the pre-release or build metadata doesn't fit in `N`
+    pub const fn try_parse(s: &str) -> Result<Self, VersionError> {
+        let (parts, end) = match Parts::parse(s, 0, false) {
+            Ok(found) => found,
+            Err(err) => return Err(err),
+        };
+        if end < s.len() {
+            return Err(VersionError::unexpected(s, end));
+        }
+        Ok(Self {
+            major: parts.major,
+            minor: parts.minor,
+            patch: parts.patch,
+            pre: KStr::from_str(parts.pre),
+            build: KStr::from_str(parts.build),
+        })
+    }
+
+    /// Compares the precedence of two versions, build metadata is ignored.
+    pub const fn cmp<const M: usize>(&self, other: &KVersion<M>) -> Ordering {
+        match cmp_u64(self.major, other.major) {
+            Ordering::Equal => {}
+            ord => return ord,
+        }
+        match cmp_u64(self.minor, other.minor) {
+            Ordering::Equal => {}
+            ord => return ord,
+        }
+        match cmp_u64(self.patch, other.patch) {
+            Ordering::Equal => cmp_pre(self.pre.as_str(), other.pre.as_str()),
+            ord => ord,
+        }
+    }
+
+    /// Returns whether the version matches every comparator of the requirement.
+    /// # Panics: panic if the requirement is malformed
+    pub const fn matches(&self, requirement: &str) -> bool {
+        match self.try_matches(requirement) {
+            Ok(matches) => matches,
+            Err(err) => err.panic(),
+        }
+    }
+
+    /// Returns whether the version matches every comparator of the requirement, or an error if it is malformed.
+    pub const fn try_matches(&self, requirement: &str) -> Result<bool, VersionError> {
+        let mut matches = true;
+        let mut pre_allowed = self.pre.is_empty();
+        let mut idx = 0;
+        loop {
+            let (comparator, end) = match Comparator::parse(requirement, idx) {
+                Ok(found) => found,
+                Err(err) => return Err(err),
+            };
+            matches = matches && comparator.matches(self);
+            pre_allowed = pre_allowed
+                || (!comparator.version.pre.is_empty()
+                    && comparator.version.major == self.major
+                    && comparator.version.minor == self.minor
+                    && comparator.version.patch == self.patch
+                    && matches!(comparator.version.precision, Precision::Patch));
+            if end == requirement.len() {
+                break;
+            }
+            idx = end + 1;
+        }
+        Ok(matches && pre_allowed)
+    }
+
+    /// Returns the version as a String, like `1.4.0-beta.2+build.5`.
+    /// # Panics: panic if the version doesn't fit in the String
+    pub const fn to_kstr<const M: usize>(&self) -> KStr<M> {
+        let mut s = KStr::new()
+            .push_u64(self.major)
+            .push(b'.')
+            .push_u64(self.minor)
+            .push(b'.')
+            .push_u64(self.patch);
+        if !self.pre.is_empty() {
+            s = s.push(b'-').extend(self.pre.as_bytes());
+        }
+        if !self.build.is_empty() {
+            s = s.push(b'+').extend(self.build.as_bytes());
+        }
+        s
+    }
+}
+
+impl<const N: usize> std::fmt::Display for KVersion<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.pre.is_empty() {
+            write!(f, "-{}", self.pre.as_str())?;
+        }
+        if !self.build.is_empty() {
+            write!(f, "+{}", self.build.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+/// The pieces of a version borrowed from the text, missing numbers are 0
+#[derive(Clone, Copy)]
+struct Parts<'a> {
+    major: u64,
+    minor: u64,
+    patch: u64,
+    pre: &'a str,
+    build: &'a str,
+    precision: Precision,
+}
+
+/// How many numbers a version in a requirement has
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Precision {
+    Major,
+    Minor,
+    Patch,
+}
+
+impl<'a> Parts<'a> {
+    /// Parses the version starting at `start` up to the first char that can't be part of it,
+    /// the minor and patch numbers can only be omitted if `partial`.
+    const fn parse(s: &'a str, start: usize, partial: bool) -> Result<(Self, usize), VersionError> {
+        let mut parts = Parts {
+            major: 0,
+            minor: 0,
+            patch: 0,
+            pre: "",
+            build: "",
+            precision: Precision::Major,
+        };
+        let mut idx;
+        (parts.major, idx) = match number(s, start) {
+            Ok(found) => found,
+            Err(err) => return Err(err),
+        };
+        if partial && !at(s, idx, b'.') {
+            return Ok((parts, idx));
+        }
+        if !at(s, idx, b'.') {
+            return Err(VersionError::unexpected(s, idx));
+        }
+        (parts.minor, idx) = match number(s, idx + 1) {
+            Ok(found) => found,
+            Err(err) => return Err(err),
+        };
+        parts.precision = Precision::Minor;
+        if partial && !at(s, idx, b'.') {
+            return Ok((parts, idx));
+        }
+        if !at(s, idx, b'.') {
+            return Err(VersionError::unexpected(s, idx));
+        }
+        (parts.patch, idx) = match number(s, idx + 1) {
+            Ok(found) => found,
+            Err(err) => return Err(err),
+        };
+        parts.precision = Precision::Patch;
+        if at(s, idx, b'-') {
+            let end = match identifiers(s, idx + 1, true) {
+                Ok(end) => end,
+                Err(err) => return Err(err),
+            };
+            parts.pre = s.split_at(end).0.split_at(idx + 1).1;
+            idx = end;
+        }
+        if at(s, idx, b'+') {
+            let end = match identifiers(s, idx + 1, false) {
+                Ok(end) => end,
+                Err(err) => return Err(err),
+            };
+            parts.build = s.split_at(end).0.split_at(idx + 1).1;
+            idx = end;
+        }
+        Ok((parts, idx))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Exact,
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq,
+    Tilde,
+    Caret,
+}
+
+/// A comparator of a requirement, like `>=1.2`
+#[derive(Clone, Copy)]
+struct Comparator<'a> {
+    op: Op,
+    version: Parts<'a>,
+}
+
+impl<'a> Comparator<'a> {
+    /// Parses the comparator starting at `start`, returns it and the index of the `,` or end after it.
+    const fn parse(s: &'a str, start: usize) -> Result<(Self, usize), VersionError> {
+        let bytes = s.as_bytes();
+        let mut idx = skip_spaces(s, start);
+        let (op, len) = match bytes.split_at(idx).1 {
+            [b'>', b'=', ..] => (Op::GreaterEq, 2),
+            [b'<', b'=', ..] => (Op::LessEq, 2),
+            [b'>', ..] => (Op::Greater, 1),
+            [b'<', ..] => (Op::Less, 1),
+            [b'=', ..] => (Op::Exact, 1),
+            [b'~', ..] => (Op::Tilde, 1),
+            [b'^', ..] => (Op::Caret, 1),
+            _ => (Op::Caret, 0),
+        };
+        idx = skip_spaces(s, idx + len);
+        let (version, end) = match Parts::parse(s, idx, true) {
+            Ok(found) => found,
+            Err(err) => return Err(err),
+        };
+        idx = skip_spaces(s, end);
+        if idx < bytes.len() && bytes[idx] != b',' {
+            return Err(VersionError::unexpected(s, idx));
+        }
+        Ok((Self { op, version }, idx))
+    }
+
+    const fn matches<const N: usize>(&self, v: &KVersion<N>) -> bool {
+        match self.op {
+            Op::Exact => self.matches_exact(v),
+            Op::Greater => self.matches_greater(v),
+            Op::GreaterEq => self.matches_exact(v) || self.matches_greater(v),
+            Op::Less => self.matches_less(v),
+            Op::LessEq => self.matches_exact(v) || self.matches_less(v),
+            Op::Tilde => self.matches_tilde(v),
+            Op::Caret => self.matches_caret(v),
+        }
+    }
+
+    /// Whether the numbers given by the comparator are equal, and the pre-releases if it has a patch
+    const fn matches_exact<const N: usize>(&self, v: &KVersion<N>) -> bool {
+        let c = &self.version;
+        match self.version.precision {
+            Precision::Major => v.major == c.major,
+            Precision::Minor => v.major == c.major && v.minor == c.minor,
+            Precision::Patch => {
+                v.major == c.major
+                    && v.minor == c.minor
+                    && v.patch == c.patch
+                    && bytes_eq(v.pre.as_bytes(), c.pre.as_bytes())
+            }
+        }
+    }
+
+    /// Whether the version is above every version matching the comparator exactly
+    const fn matches_greater<const N: usize>(&self, v: &KVersion<N>) -> bool {
+        self.cmp_given(v).is_gt()
+    }
+
+    /// Whether the version is below every version matching the comparator exactly
+    const fn matches_less<const N: usize>(&self, v: &KVersion<N>) -> bool {
+        self.cmp_given(v).is_lt()
+    }
+
+    /// Compares the version with the comparator on the numbers it gives, then the pre-release
+    const fn cmp_given<const N: usize>(&self, v: &KVersion<N>) -> Ordering {
+        let c = &self.version;
+        match cmp_u64(v.major, c.major) {
+            Ordering::Equal if matches!(self.version.precision, Precision::Major) => {
+                Ordering::Equal
+            }
+            Ordering::Equal => match cmp_u64(v.minor, c.minor) {
+                Ordering::Equal if matches!(self.version.precision, Precision::Minor) => {
+                    Ordering::Equal
+                }
+                Ordering::Equal => match cmp_u64(v.patch, c.patch) {
+                    Ordering::Equal => cmp_pre(v.pre.as_str(), c.pre),
+                    ord => ord,
+                },
+                ord => ord,
+            },
+            ord => ord,
+        }
+    }
+
+    /// `~1.2.3` is `>=1.2.3, <1.3.0`, `~1.2` is `>=1.2.0, <1.3.0` and `~1` is `>=1.0.0, <2.0.0`
+    const fn matches_tilde<const N: usize>(&self, v: &KVersion<N>) -> bool {
+        let c = &self.version;
+        match self.version.precision {
+            Precision::Major => v.major == c.major,
+            Precision::Minor => v.major == c.major && v.minor == c.minor,
+            Precision::Patch => {
+                v.major == c.major
+                    && v.minor == c.minor
+                    && (v.patch > c.patch
+                        || (v.patch == c.patch && !cmp_pre(v.pre.as_str(), c.pre).is_lt()))
+            }
+        }
+    }
+
+    /// `^1.2.3` is `>=1.2.3, <2.0.0`, `^0.2.3` is `>=0.2.3, <0.3.0` and `^0.0.3` is `=0.0.3`
+    const fn matches_caret<const N: usize>(&self, v: &KVersion<N>) -> bool {
+        let c = &self.version;
+        if v.major != c.major {
+            return false;
+        }
+        match self.version.precision {
+            Precision::Major => true,
+            Precision::Minor if c.major > 0 => v.minor >= c.minor,
+            Precision::Minor => v.minor == c.minor,
+            Precision::Patch => {
+                let patch_ok = v.patch > c.patch
+                    || (v.patch == c.patch && !cmp_pre(v.pre.as_str(), c.pre).is_lt());
+                if c.major > 0 {
+                    v.minor > c.minor || (v.minor == c.minor && patch_ok)
+                } else if c.minor > 0 {
+                    v.minor == c.minor && patch_ok
+                } else {
+                    v.minor == c.minor
+                        && v.patch == c.patch
+                        && !cmp_pre(v.pre.as_str(), c.pre).is_lt()
+                }
+            }
+        }
+    }
+}
+
+const fn at(s: &str, idx: usize, byte: u8) -> bool {
+    idx < s.len() && s.as_bytes()[idx] == byte
+}
+
+/// Parses the number starting at `start`, returns it and the index after it.
+const fn number(s: &str, start: usize) -> Result<(u64, usize), VersionError> {
+    let bytes = s.as_bytes();
+    let mut end = start;
+    while end < bytes.len() && bytes[end].is_ascii_digit() {
+        end += 1;
+    }
+    if end == start {
+        return Err(VersionError::unexpected(s, start));
+    }
+    if bytes[start] == b'0' && end - start > 1 {
+        return Err(VersionError::new(VersionErrorKind::LeadingZero, start));
+    }
+    match parse_u64(s.split_at(end).0.split_at(start).1) {
+        Ok(n) => Ok((n, end)),
+        Err(_) => Err(VersionError::new(VersionErrorKind::Overflow, start)),
+    }
+}
+
+/// Checks the dot separated identifiers starting at `start`, returns the index after them.
+/// `numeric` forbids leading zeros in the identifiers made of digits only.
+const fn identifiers(s: &str, start: usize, numeric: bool) -> Result<usize, VersionError> {
+    let bytes = s.as_bytes();
+    let mut idx = start;
+    loop {
+        let ident = idx;
+        let mut digits = true;
+        while idx < bytes.len() && (bytes[idx].is_ascii_alphanumeric() || bytes[idx] == b'-') {
+            digits = digits && bytes[idx].is_ascii_digit();
+            idx += 1;
+        }
+        if idx == ident {
+            return Err(VersionError::new(VersionErrorKind::EmptyIdentifier, ident));
+        }
+        if numeric && digits && bytes[ident] == b'0' && idx - ident > 1 {
+            return Err(VersionError::new(VersionErrorKind::LeadingZero, ident));
+        }
+        if !at(s, idx, b'.') {
+            return Ok(idx);
+        }
+        idx += 1;
+    }
+}
+
+const fn cmp_u64(a: u64, b: u64) -> Ordering {
+    if a < b {
+        Ordering::Less
+    } else if a > b {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    }
+}
+
+/// Compares two pre-releases, a release being above any pre-release.
+const fn cmp_pre(a: &str, b: &str) -> Ordering {
+    match (a.is_empty(), b.is_empty()) {
+        (true, true) => return Ordering::Equal,
+        (true, false) => return Ordering::Greater,
+        (false, true) => return Ordering::Less,
+        (false, false) => {}
+    }
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let (mut a_idx, mut b_idx) = (0, 0);
+    loop {
+        match (a_idx < a.len(), b_idx < b.len()) {
+            (false, false) => return Ordering::Equal,
+            (false, true) => return Ordering::Less,
+            (true, false) => return Ordering::Greater,
+            (true, true) => {}
+        }
+        let (a_end, a_digits) = identifier_end(a, a_idx);
+        let (b_end, b_digits) = identifier_end(b, b_idx);
+        let ord = match (a_digits, b_digits) {
+            // Numeric identifiers have no leading zeros, so the longest one is the greatest
+            (true, true) => match cmp_u64((a_end - a_idx) as u64, (b_end - b_idx) as u64) {
+                Ordering::Equal => cmp_bytes(a, a_idx, a_end, b, b_idx, b_end),
+                ord => ord,
+            },
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => cmp_bytes(a, a_idx, a_end, b, b_idx, b_end),
+        };
+        if !ord.is_eq() {
+            return ord;
+        }
+        a_idx = a_end + 1;
+        b_idx = b_end + 1;
+    }
+}
+
+/// Returns the end of the identifier starting at `start` and whether it is made of digits only.
+const fn identifier_end(s: &[u8], start: usize) -> (usize, bool) {
+    let mut idx = start;
+    let mut digits = true;
+    while idx < s.len() && s[idx] != b'.' {
+        digits = digits && s[idx].is_ascii_digit();
+        idx += 1;
+    }
+    (idx, digits)
+}
+
+/// Compares `a[a_idx..a_end]` and `b[b_idx..b_end]` lexically.
+const fn cmp_bytes(
+    a: &[u8],
+    mut a_idx: usize,
+    a_end: usize,
+    b: &[u8],
+    mut b_idx: usize,
+    b_end: usize,
+) -> Ordering {
+    while a_idx < a_end && b_idx < b_end {
+        if a[a_idx] != b[b_idx] {
+            return cmp_u64(a[a_idx] as u64, b[b_idx] as u64);
+        }
+        a_idx += 1;
+        b_idx += 1;
+    }
+    cmp_u64((a_end - a_idx) as u64, (b_end - b_idx) as u64)
+}
+
+#[cfg(test)]
+mod version_test {
+    use super::*;
+
+    const V: KVersion<16> = KVersion::parse("1.4.0-beta.2+exp.sha.5114f85");
+    const _: () = {
+        assert!(V.major == 1 && V.minor == 4 && V.patch == 0);
+        assert!(V.cmp(&KVersion::<8>::parse("1.4.0-beta.11")).is_lt());
+        assert!(V.matches(">=1.4.0-beta, <1.4.0"));
+    };
+
+    fn v(s: &str) -> KVersion<32> {
+        KVersion::try_parse(s).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let version = v("10.20.30-rc.1+build-7");
+        assert_eq!((version.major, version.minor, version.patch), (10, 20, 30));
+        assert_eq!(version.pre.as_str(), "rc.1");
+        assert_eq!(version.build.as_str(), "build-7");
+        assert_eq!(version.to_kstr::<32>().as_str(), "10.20.30-rc.1+build-7");
+        assert_eq!(version.to_string(), "10.20.30-rc.1+build-7");
+        assert_eq!(v("0.0.0+01").build.as_str(), "01");
+        assert_eq!(v("18446744073709551615.0.0").major, 18446744073709551615);
+    }
+
+    #[test]
+    fn errors() {
+        for (s, kind, offset) in [
+            ("", VersionErrorKind::UnexpectedEnd, 0),
+            ("1", VersionErrorKind::UnexpectedEnd, 1),
+            ("1.2", VersionErrorKind::UnexpectedEnd, 3),
+            ("1.2.", VersionErrorKind::UnexpectedEnd, 4),
+            ("v1.2.3", VersionErrorKind::UnexpectedChar, 0),
+            ("1.2.3 ", VersionErrorKind::UnexpectedChar, 5),
+            ("1.02.3", VersionErrorKind::LeadingZero, 2),
+            ("1.2.3-01", VersionErrorKind::LeadingZero, 6),
+            ("1.2.3-a..b", VersionErrorKind::EmptyIdentifier, 8),
+            ("1.2.3-", VersionErrorKind::EmptyIdentifier, 6),
+            ("1.2.3+", VersionErrorKind::EmptyIdentifier, 6),
+            ("1.2.3-a_b", VersionErrorKind::UnexpectedChar, 7),
+            ("18446744073709551616.0.0", VersionErrorKind::Overflow, 0),
+        ] {
+            assert_eq!(
+                KVersion::<8>::try_parse(s),
+                Err(VersionError::new(kind, offset)),
+                "{s:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn precedence() {
+        let ordered = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "1.0.1",
+            "1.1.0",
+            "2.0.0",
+        ];
+        for (i, a) in ordered.iter().enumerate() {
+            for (j, b) in ordered.iter().enumerate() {
+                assert_eq!(v(a).cmp(&v(b)), i.cmp(&j), "{a} {b}");
+            }
+        }
+        assert!(v("1.0.0+a").cmp(&v("1.0.0+b")).is_eq());
+    }
+
+    #[test]
+    fn requirements() {
+        for (req, matching, not_matching) in [
+            (
+                "^1.2.3",
+                &["1.2.3", "1.9.0"][..],
+                &["1.2.2", "2.0.0", "1.3.0-rc"][..],
+            ),
+            ("1.2", &["1.2.0", "1.5.1"], &["1.1.9", "2.0.0"]),
+            ("^0.2.3", &["0.2.3", "0.2.9"], &["0.3.0", "0.2.2"]),
+            ("^0.0.3", &["0.0.3"], &["0.0.4", "0.0.2"]),
+            ("^0.0", &["0.0.7"], &["0.1.0"]),
+            ("^0", &["0.9.9"], &["1.0.0"]),
+            ("~1.2.3", &["1.2.3", "1.2.8"], &["1.3.0", "1.2.2"]),
+            ("~1", &["1.0.0", "1.9.0"], &["2.0.0"]),
+            (">=1.2", &["1.2.0", "3.0.0"], &["1.1.9", "1.2.0-rc.1"]),
+            (">1.2", &["1.3.0"], &["1.2.9"]),
+            ("<=1.2", &["1.2.9", "0.1.0"], &["1.3.0"]),
+            ("<1.2.3", &["1.2.2"], &["1.2.3", "1.2.3-rc.1"]),
+            ("=1.2.3-rc.1", &["1.2.3-rc.1"], &["1.2.3", "1.2.3-rc.2"]),
+            (
+                ">= 1.2.3-beta , < 1.3",
+                &["1.2.3-beta", "1.2.3-rc", "1.2.5"],
+                &["1.2.3-alpha", "1.2.4-rc", "1.3.0"],
+            ),
+        ] {
+            for s in matching {
+                assert!(v(s).matches(req), "{s} {req}");
+            }
+            for s in not_matching {
+                assert!(!v(s).matches(req), "{s} {req}");
+            }
+        }
+    }
+
+    #[test]
+    fn requirement_errors() {
+        for (req, kind, offset) in [
+            ("", VersionErrorKind::UnexpectedEnd, 0),
+            (">=1,", VersionErrorKind::UnexpectedEnd, 4),
+            ("~>1", VersionErrorKind::UnexpectedChar, 1),
+            ("1.2-rc", VersionErrorKind::UnexpectedChar, 3),
+            ("1 2", VersionErrorKind::UnexpectedChar, 2),
+            ("^01", VersionErrorKind::LeadingZero, 1),
+        ] {
+            assert_eq!(
+                v("1.0.0").try_matches(req),
+                Err(VersionError::new(kind, offset)),
+                "{req:?}"
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn malformed() {
+        let _ = KVersion::<8>::parse("1.0");
+    }
+}